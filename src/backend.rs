@@ -0,0 +1,7 @@
+//Search engine used to solve a board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SolverBackend {
+	#[default]
+	Candidates, //Candidate analysis with stack-based backtracking, able to report every guess
+	DancingLinks, //Knuth's Algorithm X on an exact-cover matrix, without guess reporting
+}
@@ -0,0 +1,49 @@
+//Depth-first search that stops at each solution it reaches and carries on from there when asked for the next,
+//shared by the candidate search and Dancing Links.
+pub(crate) trait Backtrack {
+	type Solution;
+
+	//Where the search stands between solutions
+	fn progress(&mut self) -> &mut Progress;
+
+	//Solution the search is at, if it is at one
+	fn solution(&self) -> Option<Self::Solution>;
+
+	//Make the next choice, returning false at a dead end
+	fn choose(&mut self) -> bool;
+
+	//Undo choices until one has an alternative left, marking the search done once none do
+	fn backtrack(&mut self);
+}
+
+//Where a resumable search stands between solutions.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Progress {
+	pub(crate) resume: bool, //Whether the last solution still has to be backed out of
+	pub(crate) done: bool, //Whether every branch has been searched
+}
+
+//Run a search until its next solution, calling on_choice after every choice.
+//The search stops for good once on_choice returns false.
+pub(crate) fn next_solution<B: Backtrack, F: FnMut(&mut B) -> bool>(search: &mut B, on_choice: &mut F) -> Option<B::Solution> {
+
+	//Treat the last solution like a dead end so the search carries on to the next one.
+	if search.progress().resume {
+		search.progress().resume = false;
+		search.backtrack();
+	}
+
+	while !search.progress().done {
+		if let Some(solution) = search.solution() {
+			search.progress().resume = true;
+			return Some(solution);
+		}
+
+		if !search.choose() {
+			search.backtrack();
+		} else if !on_choice(search) {
+			search.progress().done = true;
+		}
+	}
+	None
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::backend::SolverBackend;
+use crate::board::Board;
+use crate::branching::Branching;
+use crate::deduction::Deduction;
+use crate::error::{ParseError, SolveError};
+use crate::parse;
+
+//Result of solving a single puzzle of a batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+	Solved(Vec<Vec<u16>>), //The one and only solution
+	NoSolution,
+	MultipleSolutions,
+	Invalid(ParseError), //The line could not be read as a puzzle
+	Rejected(SolveError), //The puzzle was read but is not a valid board
+}
+
+impl fmt::Display for Outcome {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Outcome::Solved(grid) => match parse::to_line(grid) {
+				Ok(line) => write!(f, "{}", line),
+				Err(e) => write!(f, "invalid: {}", e),
+			},
+			Outcome::NoSolution => write!(f, "no solution"),
+			Outcome::MultipleSolutions => write!(f, "multiple solutions"),
+			Outcome::Invalid(e) => write!(f, "invalid: {}", e),
+			Outcome::Rejected(e) => write!(f, "invalid: {}", e),
+		}
+	}
+}
+
+//How every puzzle of a batch is searched, see the matching Board setters.
+#[derive(Clone, Debug, Default)]
+pub struct SearchOptions {
+	pub backend: SolverBackend,
+	pub branching: Branching,
+	pub deduction: Deduction,
+}
+
+//Aggregate results of a batch.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+	pub puzzles: usize,
+	pub solved: usize,
+	pub no_solution: usize,
+	pub multiple_solutions: usize,
+	pub invalid: usize,
+	pub elapsed: Duration, //Time spent solving, excluding reading and writing, summed over every worker
+	pub wall: Duration, //Time from the start of the batch until every outcome was written
+}
+impl Stats {
+
+	//Record the outcome of one puzzle.
+	fn add(&mut self, outcome: &Outcome, elapsed: Duration) {
+		self.puzzles += 1;
+		self.elapsed += elapsed;
+		match outcome {
+			Outcome::Solved(_) => self.solved += 1,
+			Outcome::NoSolution => self.no_solution += 1,
+			Outcome::MultipleSolutions => self.multiple_solutions += 1,
+			Outcome::Invalid(_) | Outcome::Rejected(_) => self.invalid += 1,
+		}
+	}
+
+	//Puzzles finished per second of wall-clock time
+	pub fn puzzles_per_sec(&self) -> f64 {
+		let secs = self.wall.as_secs_f64();
+		if secs == 0.0 { 0.0 } else { self.puzzles as f64 / secs }
+	}
+}
+
+impl fmt::Display for Stats {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let wall_ms = self.wall.as_secs_f64() * 1000.0;
+		let avg_ms = if self.puzzles == 0 { 0.0 } else { self.elapsed.as_secs_f64() * 1000.0 / self.puzzles as f64 };
+
+		writeln!(f, "{} puzzles in {:.3}ms, {:.1} puzzles/sec ({:.3}ms solving per puzzle)",
+			self.puzzles, wall_ms, self.puzzles_per_sec(), avg_ms)?;
+		write!(f, "{} solved, {} no solution, {} multiple solutions, {} invalid",
+			self.solved, self.no_solution, self.multiple_solutions, self.invalid)
+	}
+}
+
+//Solve a single puzzle written on one line.
+pub fn solve_line(line: &str) -> Outcome {
+	solve_line_with(line, &SearchOptions::default())
+}
+
+//Solve a single puzzle written on one line using the given search options.
+pub fn solve_line_with(line: &str, options: &SearchOptions) -> Outcome {
+	let grid = match parse::line(line) {
+		Ok(grid) => grid,
+		Err(e) => return Outcome::Invalid(e),
+	};
+
+	let mut b = match Board::from_grid(&grid) {
+		Ok(b) => b,
+		Err(e) => return Outcome::Rejected(e),
+	};
+	b.set_backend(options.backend);
+	b.set_branching(options.branching.clone());
+	b.set_deduction(options.deduction);
+
+	//Bad givens are reported straight away, a cell with no candidates simply has no solution.
+	if let Err(errors) = b.validate() {
+		return match errors.into_iter().find(|e| matches!(e, SolveError::ConflictingGivens { .. })) {
+			Some(e) => Outcome::Rejected(e),
+			None => Outcome::NoSolution,
+		};
+	}
+	//Keep searching after the first solution to tell whether it is the only one.
+	let mut solutions = b.solutions().take(2);
+	match (solutions.next(), solutions.next()) {
+		(Some(solution), None) => Outcome::Solved(solution.to_grid()),
+		(None, _) => Outcome::NoSolution,
+		_ => Outcome::MultipleSolutions,
+	}
+}
+
+//Solve every puzzle of input, one per line, writing each outcome on its own line of output.
+//Blank lines and lines starting with '#' are skipped.
+pub fn solve_lines<R: BufRead, W: Write>(input: R, output: W) -> io::Result<Stats> {
+	solve_lines_with(input, output, &SearchOptions::default())
+}
+
+//Solve every puzzle of input like solve_lines() using the given search options.
+pub fn solve_lines_with<R: BufRead, W: Write>(input: R, mut output: W, options: &SearchOptions) -> io::Result<Stats> {
+	let mut stats = Stats::default();
+	let batch_start = Instant::now();
+
+	for line in input.lines() {
+		let line = line?;
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		let start = Instant::now();
+		let outcome = solve_line_with(line, options);
+		stats.add(&outcome, start.elapsed());
+
+		writeln!(output, "{}", outcome)?;
+	}
+
+	output.flush()?;
+	stats.wall = batch_start.elapsed();
+	Ok(stats)
+}
+
+//Solve every puzzle of input like solve_lines_with(), spreading the puzzles over a number of worker threads.
+//Outcomes are still written in input order. A workers of 0 uses every available core.
+pub fn solve_lines_parallel<R: BufRead, W: Write>(input: R, mut output: W, workers: usize, options: &SearchOptions) -> io::Result<Stats> {
+	let workers = match workers {
+		0 => thread::available_parallelism().map_or(1, |n| n.get()),
+		n => n,
+	};
+
+	let mut stats = Stats::default();
+	let batch_start = Instant::now();
+
+	//Puzzles go out numbered so their outcomes can be put back in order.
+	let (job_tx, job_rx) = mpsc::sync_channel::<(usize, String)>(workers*4);
+	let (done_tx, done_rx) = mpsc::channel::<(usize, Outcome, Duration)>();
+	let job_rx = Mutex::new(job_rx);
+
+	thread::scope(|scope| {
+		for _ in 0..workers {
+			let done_tx = done_tx.clone();
+			let job_rx = &job_rx;
+			scope.spawn(move || loop {
+				let job = job_rx.lock().unwrap().recv();
+				let (index, line) = match job {
+					Ok(job) => job,
+					Err(_) => return, //Every puzzle has been handed out
+				};
+
+				let start = Instant::now();
+				let outcome = solve_line_with(&line, options);
+				if done_tx.send((index, outcome, start.elapsed())).is_err() {
+					return;
+				}
+			});
+		}
+		drop(done_tx);
+
+		let mut pending: HashMap<usize, (Outcome, Duration)> = HashMap::new();
+		let mut next = 0; //Index of the next outcome to write
+		let mut sent = 0; //Number of puzzles handed out so far
+
+		//Write every outcome that is next in line.
+		let mut write_ready = |pending: &mut HashMap<usize, (Outcome, Duration)>, next: &mut usize| -> io::Result<()> {
+			while let Some((outcome, elapsed)) = pending.remove(next) {
+				stats.add(&outcome, elapsed);
+				writeln!(output, "{}", outcome)?;
+				*next += 1;
+			}
+			Ok(())
+		};
+
+		for line in input.lines() {
+			let line = line?;
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			//Blocks while every worker is busy and the queue is full.
+			job_tx.send((sent, String::from(line))).map_err(|_| io::Error::other("batch workers stopped early"))?;
+			sent += 1;
+
+			for (index, outcome, elapsed) in done_rx.try_iter() {
+				pending.insert(index, (outcome, elapsed));
+			}
+			write_ready(&mut pending, &mut next)?;
+		}
+		drop(job_tx);
+
+		//Wait for the remaining outcomes.
+		for (index, outcome, elapsed) in done_rx.iter() {
+			pending.insert(index, (outcome, elapsed));
+			write_ready(&mut pending, &mut next)?;
+		}
+		output.flush()
+	})?;
+
+	stats.wall = batch_start.elapsed();
+	Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	//One puzzle of every outcome, with a blank line and a comment that are skipped
+	const LINES: &str = "1.34.41.2.4.4.21
+123....4........
+
+# comment
+................
+12345
+11..............
+";
+
+	#[test]
+	fn outcomes() {
+		let solved: Vec<Vec<u16>> = vec![vec![1, 2, 3, 4], vec![3, 4, 1, 2], vec![2, 1, 4, 3], vec![4, 3, 2, 1]];
+		assert_eq!(solve_line("1.34.41.2.4.4.21"), Outcome::Solved(solved));
+		assert_eq!(solve_line("123....4........"), Outcome::NoSolution);
+		assert_eq!(solve_line("................"), Outcome::MultipleSolutions);
+		assert!(matches!(solve_line("12345"), Outcome::Invalid(_)));
+		assert!(matches!(solve_line("11.............."), Outcome::Rejected(SolveError::ConflictingGivens { .. })));
+	}
+
+	#[test]
+	fn counts_every_outcome() {
+		let mut output = vec![];
+		let stats = solve_lines(LINES.as_bytes(), &mut output).unwrap();
+		let output = String::from_utf8(output).unwrap();
+
+		assert_eq!(output.lines().collect::<Vec<&str>>(), vec![
+			"1234341221434321",
+			"no solution",
+			"multiple solutions",
+			"invalid: 5 cells is not a valid board size",
+			"invalid: Digit 1 appears at both row 1, column 1 and row 1, column 2",
+		]);
+		assert_eq!((stats.puzzles, stats.solved, stats.no_solution, stats.multiple_solutions, stats.invalid), (5, 1, 1, 1, 2));
+	}
+
+	#[test]
+	fn search_options_agree() {
+		let mut expected = vec![];
+		solve_lines(LINES.as_bytes(), &mut expected).unwrap();
+
+		for backend in [SolverBackend::Candidates, SolverBackend::DancingLinks] {
+			for deduction in [Deduction::Singles, Deduction::Full] {
+				let options = SearchOptions { backend, branching: Branching::FirstEmpty, deduction };
+				let mut output = vec![];
+				solve_lines_with(LINES.as_bytes(), &mut output, &options).unwrap();
+				assert_eq!(output, expected);
+			}
+		}
+	}
+	#[test]
+	fn parallel_keeps_order() {
+		//Mix in 9x9 puzzles of differing difficulty, so workers finish out of order.
+		let mut input = String::new();
+		for _ in 0..8 {
+			input.push_str(LINES);
+			input.push_str("..76.594.........68..1...........2...7..9......9..453..1.5..36......6..7..3.....2\n");
+			input.push_str(".4587.9.....9.....2.8.6...4.1.2..4..93.5472....469.7.3.6.48..3138.7.26.9.....6.27\n");
+		}
+
+		let options = SearchOptions::default();
+		let mut expected = vec![];
+		let sequential = solve_lines_with(input.as_bytes(), &mut expected, &options).unwrap();
+		for workers in [1, 4] {
+			let mut output = vec![];
+			let stats = solve_lines_parallel(input.as_bytes(), &mut output, workers, &options).unwrap();
+			assert_eq!(output, expected);
+			assert_eq!((stats.puzzles, stats.solved, stats.no_solution, stats.multiple_solutions, stats.invalid),
+				(sequential.puzzles, sequential.solved, sequential.no_solution, sequential.multiple_solutions, sequential.invalid));
+		}
+		assert_eq!((sequential.puzzles, sequential.solved), (56, 24));
+	}
+
+	//Writer that refuses every write
+	struct Closed;
+	impl Write for Closed {
+		fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+			Err(io::Error::from(io::ErrorKind::BrokenPipe))
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn parallel_returns_write_errors() {
+		let input = LINES.repeat(20);
+		let error = solve_lines_parallel(input.as_bytes(), Closed, 4, &SearchOptions::default()).unwrap_err();
+		assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+	}
+}
@@ -0,0 +1,1351 @@
+use std::fmt;
+use std::sync::Arc;
+
+use colored::Colorize;
+
+use crate::backend::SolverBackend;
+use crate::backtrack::{self, Backtrack, Progress};
+use crate::branching::Branching;
+use crate::console;
+use crate::digits::{self, DigitBits, DigitSet, WideSet};
+use crate::deduction::Deduction;
+use crate::dlx::Dlx;
+use crate::error::SolveError;
+use crate::logic::{self, Rating, Step};
+use crate::parallel;
+use crate::topology::Topology;
+
+//Call a method generic over the set width with the width of a board's sets, as in
+//by_width!(self.sets, S => self.update_cand::<S>(coord)).
+macro_rules! by_width {
+	($sets:expr, $S:ident => $call:expr) => {
+		match $sets {
+			Width::Narrow(_) => {
+				type $S = DigitSet;
+				$call
+			}
+			Width::Wide(_) => {
+				type $S = WideSet;
+				$call
+			}
+		}
+	};
+}
+
+//Individual cell holding its digit, its candidates live in the board's Sets and its aoe information in its Topology.
+#[derive(Clone)]
+pub struct Cell {
+	digit: u16, //Digit of cell
+	was_empty: bool,
+	guessed: bool, //Whether backtracking placed the digit as a guess
+	known: bool, //Whether the guess was the cell's last candidate
+}
+impl Cell {
+
+	//Constructor
+	pub fn new() -> Self {
+		Self {
+			digit: 0,
+			was_empty: false,
+			guessed: false,
+			known: false,
+		}
+	}
+}
+
+impl Default for Cell {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+//Entire board containing size information and 2d vector of cells.
+#[derive(Clone)]
+pub struct Board {
+	bsize: usize, //Board side-length
+	hsize: usize, //House side-length
+	last_modified: [usize; 3], //Information about the last-modified cell.
+	topo: Arc<Topology>, //Peers of every cell, shared rather than cloned
+	cell: Vec<Vec<Cell>>, //2D vector containing all cells
+	sets: Width, //Candidates of every cell and digits placed in every unit
+	empty: usize, //Number of cells without a digit
+	trail: Vec<Change>, //Every change made since the trail was last cleared, most recent last
+	branching: Branching, //How backtracking picks the cell to guess on
+	backend: SolverBackend, //Search engine used by solve() and solutions()
+	deduction: Deduction, //How much candidate analysis backtracking runs after every guess
+	assume_unique: bool, //Whether solve_logically() may rely on the puzzle having a single solution
+}
+
+//A single reversible change to a board, recorded on its trail.
+#[derive(Clone, Copy)]
+enum Change {
+	Digit([usize; 2]), //A digit was placed in an empty cell
+	Cand([usize; 2]), //A cell's candidates were replaced, the previous candidates are saved in the board's Sets
+	Limit([usize; 2]), //A cell's cand_limit was extended, the previous cand_limit is saved in the board's Sets
+	Guess([usize; 2]), //A cell's digit was marked as a guess
+}
+
+//Candidates of every cell and digits placed in every unit, in digit sets of a single width.
+#[derive(Clone)]
+struct Sets<S> {
+	cand: Vec<Vec<S>>, //Candidates of each cell
+	cand_limit: Vec<Vec<S>>, //Restrictions on the candidates of each cell
+	used: [Vec<S>; 3], //Digits placed in each row, column and house, houses numbered left-to-right then top-to-bottom
+	saved: Vec<S>, //Set replaced by each Cand and Limit change on the trail, most recent last
+}
+impl<S: DigitBits> Sets<S> {
+
+	//Constructor
+	fn new(bsize: usize) -> Self {
+		Self {
+			cand: vec![vec![S::empty(bsize); bsize]; bsize],
+			cand_limit: vec![vec![S::empty(bsize); bsize]; bsize],
+			used: [vec![S::empty(bsize); bsize], vec![S::empty(bsize); bsize], vec![S::empty(bsize); bsize]],
+			saved: vec![],
+		}
+	}
+}
+
+//Sets of a board, in DigitSets when they can hold every digit of the board.
+#[derive(Clone)]
+enum Width {
+	Narrow(Sets<DigitSet>), //Boards up to digits::MAX_SIZE
+	Wide(Sets<WideSet>), //Larger boards
+}
+
+//Digit set a board can keep its Sets in.
+trait Store: DigitBits {
+	fn sets(width: &Width) -> &Sets<Self>;
+	fn sets_mut(width: &mut Width) -> &mut Sets<Self>;
+}
+
+impl Store for DigitSet {
+	fn sets(width: &Width) -> &Sets<Self> {
+		match width {
+			Width::Narrow(sets) => sets,
+			Width::Wide(_) => unreachable!("wide board read as narrow"),
+		}
+	}
+
+	fn sets_mut(width: &mut Width) -> &mut Sets<Self> {
+		match width {
+			Width::Narrow(sets) => sets,
+			Width::Wide(_) => unreachable!("wide board read as narrow"),
+		}
+	}
+}
+
+impl Store for WideSet {
+	fn sets(width: &Width) -> &Sets<Self> {
+		match width {
+			Width::Wide(sets) => sets,
+			Width::Narrow(_) => unreachable!("narrow board read as wide"),
+		}
+	}
+
+	fn sets_mut(width: &mut Width) -> &mut Sets<Self> {
+		match width {
+			Width::Wide(sets) => sets,
+			Width::Narrow(_) => unreachable!("narrow board read as wide"),
+		}
+	}
+}
+
+//A solved board returned by Board::solve().
+#[derive(Clone)]
+pub struct Solution {
+	board: Board, //The solved board, including how each cell was solved
+}
+impl Solution {
+
+	//The solved board
+	pub fn board(&self) -> &Board {
+		&self.board
+	}
+
+	//The solved digits as a 2D vector
+	pub fn to_grid(&self) -> Vec<Vec<u16>> {
+		self.board.to_grid()
+	}
+}
+
+//Board reached by Board::solve_logically(), with every named step taken to get there.
+#[derive(Clone)]
+pub struct LogicalSolution {
+	board: Board, //Board once candidate analysis ran out of steps, solved or not
+	steps: Vec<Step>, //Named steps in the order they were applied
+}
+impl LogicalSolution {
+
+	//The board once candidate analysis ran out of steps
+	pub fn board(&self) -> &Board {
+		&self.board
+	}
+
+	//Named steps in the order they were applied, not counting singles
+	pub fn steps(&self) -> &[Step] {
+		&self.steps
+	}
+
+	//Whether candidate analysis solved the board without guessing
+	pub fn is_solved(&self) -> bool {
+		self.board.is_solved()
+	}
+
+	//Hardest technique needed, or Rating::Guessing if candidate analysis was not enough
+	pub fn rating(&self) -> Rating {
+		if !self.is_solved() {
+			return Rating::Guessing;
+		}
+		match self.steps.iter().map(|step| step.technique).max_by_key(|technique| technique.difficulty()) {
+			Some(technique) => Rating::Technique(technique),
+			None => Rating::Singles,
+		}
+	}
+}
+
+//Number of solutions found by Board::count_solutions().
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolutionCount {
+	Exactly(usize), //The search finished, so this is every solution
+	AtLeast(usize), //The search stopped once the limit was reached
+}
+impl SolutionCount {
+
+	//Whether the board has exactly one solution
+	pub fn is_unique(&self) -> bool {
+		*self == SolutionCount::Exactly(1)
+	}
+}
+
+impl fmt::Display for SolutionCount {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SolutionCount::Exactly(count) => write!(f, "{}", count),
+			SolutionCount::AtLeast(count) => write!(f, "at least {}", count),
+		}
+	}
+}
+
+impl Board {
+
+	//Constructor for an empty board, the board side-length must be a square number that fits in a u16.
+	pub fn new(bsize: usize) -> Result<Self, SolveError> {
+		Board::from_grid(&vec![vec![0; bsize]; bsize])
+	}
+
+	//Board without any cells, to be filled in by init().
+	fn blank(bsize: usize) -> Result<Self, SolveError> {
+		let hsize = (bsize as f64).sqrt() as usize;
+		if bsize == 0 || hsize*hsize != bsize {
+			return Err(SolveError::NotSquare(bsize));
+		}
+		if bsize > u16::MAX as usize {
+			return Err(SolveError::TooLarge { bsize, max: u16::MAX as usize });
+		}
+
+		Ok(Self {
+			bsize,
+			hsize,
+			last_modified: [0,0,0],
+			topo: Arc::new(Topology::new(bsize, hsize)),
+			cell: vec![],
+			sets: if bsize > digits::MAX_SIZE { Width::Wide(Sets::new(bsize)) } else { Width::Narrow(Sets::new(bsize)) },
+			empty: bsize*bsize,
+			trail: vec![],
+			branching: Branching::default(),
+			backend: SolverBackend::default(),
+			deduction: Deduction::default(),
+			assume_unique: false,
+		})
+	}
+
+	//Build a board from a 2D vector of digits, where 0 is an empty cell.
+	pub fn from_grid(grid: &[Vec<u16>]) -> Result<Self, SolveError> {
+		let mut b = Board::blank(grid.len())?;
+
+		//Ensure every row is as long as the board and every digit fits on it.
+		for (i, row) in grid.iter().enumerate() {
+			if row.len() != b.bsize {
+				return Err(SolveError::RaggedRows { row: i, len: row.len() });
+			}
+			for (j, &digit) in row.iter().enumerate() {
+				if digit as usize > b.bsize {
+					return Err(SolveError::DigitOutOfRange { cell: [i, j], digit });
+				}
+			}
+		}
+
+		b.init(grid); //Initialize cells and area coordinates
+		by_width!(b.sets, S => b.update_all_cand::<S>()); //Update the candidates for all cells
+		b.clear_trail(); //Nothing to rewind past the givens
+		Ok(b)
+	}
+
+	//Board from pencilmarks, one whitespace-separated token per cell in row-major order. A single digit is
+	//placed in its cell, and any other token lists the candidates of an empty cell.
+	#[cfg(test)]
+	pub(crate) fn from_pencilmarks(text: &str) -> Self {
+		let tokens: Vec<&str> = text.split_whitespace().collect();
+		let bsize = (tokens.len() as f64).sqrt() as usize;
+		let mut b = Board::new(bsize).unwrap();
+
+		for (k, token) in tokens.iter().enumerate() {
+			let coord = [k/bsize, k%bsize];
+			let digits: DigitSet = token.chars().map(|c| c.to_digit(10).unwrap() as u16).collect();
+			if token.len() == 1 {
+				b.set_digit::<DigitSet>(coord, digits.first().unwrap());
+			} else {
+				//Rule out every other digit, so placing digits later doesn't bring candidates back.
+				let sets = b.sets_mut::<DigitSet>();
+				sets.cand[coord[0]][coord[1]] = digits;
+				sets.cand_limit[coord[0]][coord[1]] = DigitSet::full(bsize) - digits;
+			}
+		}
+		b.clear_trail();
+		b
+	}
+
+	//Board side-length
+	pub fn size(&self) -> usize {
+		self.bsize
+	}
+
+	//House side-length
+	pub fn house_size(&self) -> usize {
+		self.hsize
+	}
+
+	//Digit of a cell, 0 if empty
+	pub fn digit(&self, row: usize, col: usize) -> u16 {
+		self.cell[row][col].digit
+	}
+
+	//Candidates of a cell, empty if the cell has a digit
+	pub fn candidates(&self, row: usize, col: usize) -> Vec<u16> {
+		if self.cell[row][col].digit != 0 {
+			return vec![];
+		}
+		by_width!(self.sets, S => self.sets::<S>().cand[row][col].digits().collect())
+	}
+
+	//Candidates of a cell as a DigitSet, empty if the cell has a digit. Always empty on boards over
+	//digits::MAX_SIZE, which a DigitSet can't hold, so use candidates() there.
+	pub fn candidate_set(&self, row: usize, col: usize) -> DigitSet {
+		match &self.sets {
+			Width::Narrow(sets) if self.cell[row][col].digit == 0 => sets.cand[row][col],
+			_ => DigitSet::EMPTY,
+		}
+	}
+
+	//How backtracking picks the cell to guess on
+	pub fn branching(&self) -> &Branching {
+		&self.branching
+	}
+
+	//Choose how backtracking picks the cell to guess on, Branching::FewestCandidates by default.
+	pub fn set_branching(&mut self, branching: Branching) {
+		self.branching = branching;
+	}
+
+	//Search engine used by solve() and solutions()
+	pub fn backend(&self) -> SolverBackend {
+		self.backend
+	}
+
+	//Choose the search engine, SolverBackend::Candidates by default.
+	pub fn set_backend(&mut self, backend: SolverBackend) {
+		self.backend = backend;
+	}
+
+	//How much candidate analysis backtracking runs after every guess
+	pub fn deduction(&self) -> Deduction {
+		self.deduction
+	}
+
+	//Choose how much candidate analysis backtracking runs after every guess, Deduction::Singles by default.
+	//More analysis means fewer guesses but slower ones.
+	pub fn set_deduction(&mut self, deduction: Deduction) {
+		self.deduction = deduction;
+	}
+
+	//Whether solve_logically() may rely on the puzzle having a single solution
+	pub fn assume_unique(&self) -> bool {
+		self.assume_unique
+	}
+
+	//Let solve_logically() use unique rectangles and BUG+1, off by default. These only hold on puzzles with
+	//a single solution and may rule out every solution of any other puzzle.
+	pub fn set_assume_unique(&mut self, assume_unique: bool) {
+		self.assume_unique = assume_unique;
+	}
+
+	//Whether the board is too large for a DigitSet, so its Sets are made of WideSets
+	fn is_wide(&self) -> bool {
+		matches!(self.sets, Width::Wide(_))
+	}
+
+	//Candidates and used digits, in the board's set width
+	fn sets<S: Store>(&self) -> &Sets<S> {
+		S::sets(&self.sets)
+	}
+
+	fn sets_mut<S: Store>(&mut self) -> &mut Sets<S> {
+		S::sets_mut(&mut self.sets)
+	}
+
+	//Peers and units of every cell
+	pub(crate) fn topology(&self) -> &Topology {
+		&self.topo
+	}
+
+	//Whether every cell has a digit
+	pub fn is_solved(&self) -> bool {
+		self.empty == 0
+	}
+
+	//All digits as a 2D vector
+	pub fn to_grid(&self) -> Vec<Vec<u16>> {
+		self.cell.iter().map(|row| row.iter().map(|c| c.digit).collect()).collect()
+	}
+
+	//Initialize values of board from given input, where init is the sudoku board.
+	fn init(&mut self, init: &[Vec<u16>]) {
+
+		//Iterate through row
+		for (i, row) in init.iter().enumerate() {
+
+			//Initialize row
+			self.cell.push(vec![Cell::new(); self.bsize]);
+
+			//Iterate through column
+			for (j, &digit) in row.iter().enumerate() {
+
+				//Assign digit to cell
+				by_width!(self.sets, S => self.set_digit::<S>([i, j], digit));
+
+				if digit == 0 {
+					self.cell[i][j].was_empty = true;
+				}
+			}
+		}
+	}
+
+	//Check the givens before searching, reporting every pair of equal givens that share a row,
+	//column or house, and every empty cell with no candidates.
+	pub fn validate(&self) -> Result<(), Vec<SolveError>> {
+		let mut errors: Vec<SolveError> = vec![];
+
+		//Iterate through cells
+		for i in 0..self.bsize {
+			for j in 0..self.bsize {
+				let digit = self.cell[i][j].digit;
+
+				//Ensure an empty cell has somewhere to go
+				if digit == 0 {
+					if by_width!(self.sets, S => self.sets::<S>().cand[i][j].is_empty()) {
+						errors.push(SolveError::NoCandidates { cell: [i, j] });
+					}
+					continue;
+				}
+
+				//Check each area, only looking forward so each pair is found once.
+				let peers = &self.topo.peers[i][j];
+				for area in [&peers.row, &peers.col, &peers.house] {
+					for each in area {
+						if self.cell[each[0]][each[1]].digit != digit || (each[0], each[1]) < (i, j) {
+							continue;
+						}
+
+						//A pair sharing both a house and a line is only reported once.
+						let conflict = SolveError::ConflictingGivens { cell_a: [i, j], cell_b: *each, digit };
+						if !errors.contains(&conflict) {
+							errors.push(conflict);
+						}
+					}
+				}
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+
+	//Show current state of board
+	pub fn show(&self) {
+
+		let mut output = String::from("");
+
+		//How much space, including whitespace, each digit needs.
+		let space_per_digit = ((self.bsize as f64).log10()+2.0).floor() as usize;
+
+		console::clear();
+		output.push('\n');
+
+		output.push_str(&format!("{} - Original puzzle\n", "White\t"));
+		output.push_str(&format!("{} - Solved via candidate analysis\n", "Red\t".red()));
+		output.push_str(&format!("{} - Current backtracking cell\n", "Blue\t".cyan()));
+		output.push_str(&format!("{} - Solved via backtracking, other candidates still exist\n", "Yellow\t".yellow()));
+		output.push_str(&format!("{} - Solved via backtracking, no more candidates exist\n\n", "Green\t".green()));
+
+		//Main loop
+		for i in 0..self.bsize {
+			for j in 0..self.bsize {
+
+				//Ensure enough white-space before digit.
+				if self.cell[i][j].digit != 0 {
+					for _ in 0..space_per_digit-(((self.cell[i][j].digit).checked_ilog10().unwrap_or(0)+2) as usize) {
+						output.push(' ');
+					}
+
+					//Color cell depending on if it was solved via backtracking or candidate elimination.
+					if self.cell[i][j].known {
+						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().green()));
+					} else if i == self.last_modified[0] && j == self.last_modified[1] {
+						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().cyan()));
+					} else if self.cell[i][j].guessed {
+						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().yellow()));
+					} else if self.cell[i][j].was_empty {
+						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().red()));
+					} else {
+						output.push_str(&self.cell[i][j].digit.to_string());
+					}
+				} else {
+					for _ in 0..space_per_digit-1 {
+						output.push(' ');
+					}
+				}
+
+				//Add vertical line when end of house is reached.
+				if (j+1) % self.hsize == 0 && (j+1) != (self.bsize) {
+					output.push('|');
+				} else {
+					output.push(' ');
+				}
+			}
+			output.push('\n');
+
+			//Add horizontal line when end of house is reached.
+			if (i+1) % self.hsize == 0 && (i+1) != (self.bsize) {
+				for k in 0..self.hsize {
+					for _ in 0..(self.hsize*space_per_digit)-1 {
+						output.push('―');
+					}
+					if k != self.hsize-1 {
+						output.push('+');
+					}
+				}
+				output.push('\n');
+			}
+		}
+
+		print!("{}", output);
+	}
+
+	//Empty cell for backtracking to guess on next, chosen by the board's branching strategy.
+	fn branch_cell<S: Store>(&self) -> [usize; 2] {
+		match &self.branching {
+			Branching::FirstEmpty => self.first_empty(),
+			Branching::FewestCandidates => self.fewest_cand::<S, _>(self.topo.units.iter().take(self.bsize).flatten()),
+			Branching::MostConstrainedUnit => self.most_constrained_unit::<S>(),
+			Branching::Custom(f) => {
+				let coord = f(self);
+				if coord[0] < self.bsize && coord[1] < self.bsize && self.cell[coord[0]][coord[1]].digit == 0 {
+					coord
+				} else {
+					self.fewest_cand::<S, _>(self.topo.units.iter().take(self.bsize).flatten())
+				}
+			}
+		}
+	}
+
+	//First empty cell in row-major order.
+	fn first_empty(&self) -> [usize; 2] {
+		for i in 0..self.bsize {
+			for j in 0..self.bsize {
+				if self.cell[i][j].digit == 0 {
+					return [i, j];
+				}
+			}
+		}
+		[0, 0]
+	}
+
+	//Empty cell with the fewest candidates out of some cells, the first one on ties.
+	fn fewest_cand<'a, S: Store, I: Iterator<Item = &'a [usize; 2]>>(&self, cells: I) -> [usize; 2] {
+		let mut best: Option<([usize; 2], usize)> = None;
+		let cand = &self.sets::<S>().cand;
+
+		for each in cells {
+			if self.cell[each[0]][each[1]].digit != 0 {
+				continue;
+			}
+
+			let len = cand[each[0]][each[1]].len();
+			if best.is_none_or(|(_, best_len)| len < best_len) {
+				best = Some((*each, len));
+
+				//A cell with no candidates is a dead end, so there is nothing better.
+				if len == 0 {
+					break;
+				}
+			}
+		}
+
+		best.map_or([0, 0], |(coord, _)| coord)
+	}
+
+	//Cell with the fewest candidates in the unit with the fewest empty cells.
+	fn most_constrained_unit<S: Store>(&self) -> [usize; 2] {
+		let mut best: Option<(&Vec<[usize; 2]>, usize)> = None;
+
+		for unit in &self.topo.units {
+			let empty = unit.iter().filter(|each| self.cell[each[0]][each[1]].digit == 0).count();
+			if empty > 0 && best.is_none_or(|(_, best_empty)| empty < best_empty) {
+				best = Some((unit, empty));
+			}
+		}
+
+		best.map_or([0, 0], |(unit, _)| self.fewest_cand::<S, _>(unit.iter()))
+	}
+
+	//Index of the house containing a cell
+	fn house_of(&self, coord: [usize; 2]) -> usize {
+		(coord[0]/self.hsize)*self.hsize + coord[1]/self.hsize
+	}
+
+	//Place a digit in an empty cell and mark it as used in the cell's row, column and house.
+	fn set_digit<S: Store>(&mut self, coord: [usize; 2], digit: u16) {
+		if digit == 0 {
+			return;
+		}
+
+		self.cell[coord[0]][coord[1]].digit = digit;
+		self.empty -= 1;
+		self.trail.push(Change::Digit(coord));
+
+		let h = self.house_of(coord);
+		let used = &mut self.sets_mut::<S>().used;
+		used[0][coord[0]].insert(digit);
+		used[1][coord[1]].insert(digit);
+		used[2][h].insert(digit);
+	}
+
+	//Revert a cell to a 0 and free its digit in the cell's row, column and house.
+	fn clear_digit<S: Store>(&mut self, coord: [usize; 2]) {
+		let digit = self.cell[coord[0]][coord[1]].digit;
+		self.cell[coord[0]][coord[1]].digit = 0;
+		self.empty += 1;
+
+		let h = self.house_of(coord);
+		let used = &mut self.sets_mut::<S>().used;
+		used[0][coord[0]].remove(digit);
+		used[1][coord[1]].remove(digit);
+		used[2][h].remove(digit);
+	}
+
+	//Replace the candidates of a cell.
+	fn set_cand<S: Store>(&mut self, coord: [usize; 2], cand: S) {
+		let sets = self.sets_mut::<S>();
+		if sets.cand[coord[0]][coord[1]] != cand {
+			let prev = std::mem::replace(&mut sets.cand[coord[0]][coord[1]], cand);
+			sets.saved.push(prev);
+			self.trail.push(Change::Cand(coord));
+		}
+	}
+
+	//Rule a digit out of a cell for the rest of this branch of the search.
+	fn limit_cand<S: Store>(&mut self, coord: [usize; 2], digit: u16) {
+		let sets = self.sets_mut::<S>();
+		let prev = sets.cand_limit[coord[0]][coord[1]].clone();
+		sets.cand_limit[coord[0]][coord[1]].insert(digit);
+		sets.saved.push(prev);
+		self.trail.push(Change::Limit(coord));
+
+		let mut cand = self.sets::<S>().cand[coord[0]][coord[1]].clone();
+		cand.remove(digit);
+		self.set_cand::<S>(coord, cand);
+	}
+
+	//Mark a cell's digit as a guess, known when it was the cell's last candidate.
+	fn set_guessed(&mut self, coord: [usize; 2], known: bool) {
+		self.cell[coord[0]][coord[1]].guessed = true;
+		self.cell[coord[0]][coord[1]].known = known;
+		self.trail.push(Change::Guess(coord));
+	}
+
+	//Undo every change recorded on the trail after mark, most recent first.
+	fn rewind<S: Store>(&mut self, mark: usize) {
+		while self.trail.len() > mark {
+			match self.trail.pop().unwrap() {
+				Change::Digit(coord) => self.clear_digit::<S>(coord),
+				Change::Cand(coord) => {
+					let sets = self.sets_mut::<S>();
+					sets.cand[coord[0]][coord[1]] = sets.saved.pop().unwrap();
+				}
+				Change::Limit(coord) => {
+					let sets = self.sets_mut::<S>();
+					sets.cand_limit[coord[0]][coord[1]] = sets.saved.pop().unwrap();
+				}
+				Change::Guess(coord) => {
+					self.cell[coord[0]][coord[1]].guessed = false;
+					self.cell[coord[0]][coord[1]].known = false;
+				}
+			}
+		}
+	}
+
+	//Forget every change made so far, so nothing can be rewound past this point.
+	fn clear_trail(&mut self) {
+		self.trail.clear();
+		by_width!(self.sets, S => self.sets_mut::<S>().saved.clear());
+	}
+
+	//Digits not yet used in any of a cell's areas, restricted by cand_limit.
+	fn free_digits<S: Store>(&self, coord: [usize; 2]) -> S {
+		let sets = self.sets::<S>();
+		S::full(self.bsize)
+			.minus(&sets.used[0][coord[0]])
+			.minus(&sets.used[1][coord[1]])
+			.minus(&sets.used[2][self.house_of(coord)])
+			.minus(&sets.cand_limit[coord[0]][coord[1]])
+	}
+
+	//Candidates that appear in exactly one empty cell of each row, column and house.
+	fn unit_singles<S: Store>(&self) -> [Vec<S>; 3] {
+		let empty = vec![S::empty(self.bsize); self.bsize];
+		let mut once = [empty.clone(), empty.clone(), empty];
+		let mut twice = once.clone();
+		let sets = self.sets::<S>();
+
+		//Iterate through cells
+		for i in 0..self.bsize {
+			for j in 0..self.bsize {
+				if self.cell[i][j].digit == 0 {
+					let cand = &sets.cand[i][j];
+					for (u, unit) in [i, j, self.house_of([i, j])].into_iter().enumerate() {
+						twice[u][unit] = twice[u][unit].or(&once[u][unit].and(cand));
+						once[u][unit] = once[u][unit].or(cand);
+					}
+				}
+			}
+		}
+
+		for u in 0..3 {
+			for unit in 0..self.bsize {
+				once[u][unit] = once[u][unit].minus(&twice[u][unit]);
+			}
+		}
+		once
+	}
+
+	//Updates the candidates of all cells in a cell's aoe, restricted by cand_limit.
+	fn update_cand<S: Store>(&mut self, coord: [usize; 2]) {
+		let mut each: [usize; 2];
+
+		for k in 0..self.topo.peers[coord[0]][coord[1]].aoe.len() {
+			each = self.topo.peers[coord[0]][coord[1]].aoe[k];
+			if self.cell[each[0]][each[1]].digit == 0 {
+
+				//Assign all candidates, restricted by limit and cand_limit.
+				self.set_cand::<S>(each, self.free_digits::<S>(each));
+
+				//If there is only 1 candidate, set it as the digit and restart.
+				let cand = &self.sets::<S>().cand[each[0]][each[1]];
+				if cand.len() == 1 {
+					self.set_digit::<S>(each, cand.first().unwrap());
+					self.update_cand::<S>(each);
+				}
+			}
+		}
+	}
+
+	//Updates the candidates of all cells, restricted by cand_limit.
+	fn update_all_cand<S: Store>(&mut self) {
+
+		//Iterate through cells
+		for i in 0..self.bsize {
+			for j in 0..self.bsize {
+				//Ensure cell is a 0
+				if self.cell[i][j].digit == 0 {
+					//Assign all candidates, restricted by limit and cand_limit.
+					self.set_cand::<S>([i, j], self.free_digits::<S>([i, j]));
+				}
+			}
+		}
+
+	}
+
+
+	//Rule out the eliminations of a step, placing any cell left with a single candidate.
+	//Named steps are only found on boards narrow enough for a DigitSet.
+	pub(crate) fn apply_step(&mut self, step: &Step) {
+		for &(coord, digit) in &step.eliminations {
+			if self.cell[coord[0]][coord[1]].digit == 0 {
+				self.limit_cand::<DigitSet>(coord, digit);
+			}
+		}
+
+		for &(coord, _) in &step.eliminations {
+			let cand = self.candidate_set(coord[0], coord[1]);
+			if cand.len() == 1 {
+				self.set_digit::<DigitSet>(coord, cand.first().unwrap());
+				self.update_cand::<DigitSet>(coord);
+			}
+		}
+	}
+
+	//Checks for cells that have candidates that are unique to one of its areas, then for steps
+	//of the techniques the board's deduction allows once no such cells are left.
+	fn process_of_elimination(&mut self) {
+		self.deduce(self.deduction, false, &mut |_| {});
+	}
+
+	//Run candidate analysis up to deduction until it runs out of steps, calling on_step after every named step.
+	//Uniqueness techniques are only used when unique is set. Named techniques read DigitSets, so boards over
+	//digits::MAX_SIZE stop at singles.
+	fn deduce<F: FnMut(Step)>(&mut self, deduction: Deduction, unique: bool, on_step: &mut F) {
+		loop {
+			by_width!(self.sets, S => self.hidden_singles::<S>());
+			if deduction == Deduction::Singles || self.is_wide() {
+				break;
+			}
+
+			//Go back to singles after every step.
+			match logic::find_step(self, deduction, unique) {
+				Some(step) => {
+					self.apply_step(&step);
+					on_step(step);
+				}
+				None => break,
+			}
+		}
+	}
+
+	//Place every candidate that is unique to one of its cell's areas, and the last candidate of any cell.
+	fn hidden_singles<S: Store>(&mut self) {
+		let mut unique: Option<u16>; //Candidate missing from one of the cell's areas
+		let mut reset: bool = true; //Whether or not to keep searching
+
+		//Start search
+		while reset {
+			reset = false;
+
+			//Candidates can only be removed during a pass, so a digit found once stays unique.
+			let [row, col, house] = self.unit_singles::<S>();
+
+			//Iterate through all cells
+			for i in 0..self.bsize {
+				for j in 0..self.bsize {
+
+					//Ensure cell is a 0
+					if self.cell[i][j].digit == 0 {
+
+						//If areas do not contain candidate, or it is the cell's last one, then set cell to candidate.
+						let cand = &self.sets::<S>().cand[i][j];
+						unique = cand.and(&row[i].or(&col[j]).or(&house[self.house_of([i, j])])).first();
+						if cand.len() == 1 {
+							unique = cand.first();
+						}
+
+						if let Some(c) = unique {
+							self.set_digit::<S>([i, j], c);
+							self.update_cand::<S>([i, j]);
+							reset = true;
+						}
+					}
+				}
+			}
+		}
+	}
+
+	//Solve the board using the board's backend.
+	pub fn solve(&self) -> Result<Solution, SolveError> {
+		self.solve_with(|_| {})
+	}
+
+	//Solve the board, calling on_guess with the current board after every backtracking guess.
+	//The DancingLinks backend does not report its guesses.
+	pub fn solve_with<F: FnMut(&Board)>(&self, mut on_guess: F) -> Result<Solution, SolveError> {
+
+		//Report bad givens instead of searching a board that can never be solved.
+		if let Err(mut errors) = self.validate() {
+			return Err(errors.swap_remove(0));
+		}
+
+		self.solutions().next_with(&mut on_guess).ok_or(SolveError::Unsolvable)
+	}
+
+	//Solve the board using candidate analysis alone, without guessing, recording every named step taken.
+	pub fn solve_logically(&self) -> Result<LogicalSolution, SolveError> {
+		if let Err(mut errors) = self.validate() {
+			return Err(errors.swap_remove(0));
+		}
+
+		if self.is_wide() {
+			return Err(SolveError::LogicTooLarge { bsize: self.bsize, max: digits::MAX_SIZE });
+		}
+
+		let mut board = self.clone();
+		let mut steps = vec![];
+		board.update_all_cand::<DigitSet>();
+		board.deduce(Deduction::Full, self.assume_unique, &mut |step| steps.push(step));
+		board.clear_trail();
+		Ok(LogicalSolution { board, steps })
+	}
+
+	//Solve the board using candidate analysis, searching separate branches on up to threads threads.
+	//A board with several solutions may return any of them. A threads of 0 uses every available core.
+	pub fn solve_parallel(&self, threads: usize) -> Result<Solution, SolveError> {
+		if let Err(mut errors) = self.validate() {
+			return Err(errors.swap_remove(0));
+		}
+
+		let threads = match threads {
+			0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+			n => n,
+		};
+		if threads == 1 {
+			return self.solve();
+		}
+
+		let mut b = self.clone();
+		b.backend = SolverBackend::Candidates;
+		parallel::search(b.solutions(), threads).ok_or(SolveError::Unsolvable)
+	}
+
+	//Count solutions of the board, stopping once limit solutions have been found.
+	//A limit of 2 is enough to tell whether a puzzle is proper.
+	pub fn count_solutions(&self, limit: usize) -> SolutionCount {
+		let count = self.solutions().take(limit).count();
+
+		if count >= limit {
+			SolutionCount::AtLeast(count)
+		} else {
+			SolutionCount::Exactly(count)
+		}
+	}
+
+	//Every solution of the board, found lazily by resuming the search on each call to next().
+	//Boards with bad givens have no solutions.
+	pub fn solutions(&self) -> Solutions {
+		let mut b = self.clone(); //The main board
+		let mut dlx = None;
+		b.clear_trail();
+
+		//Bad givens would take forever to search.
+		let done = self.validate().is_err();
+		if !done {
+			match self.backend {
+				//Built from the givens alone, independent of candidate analysis.
+				SolverBackend::DancingLinks => {
+					by_width!(b.sets, S => {
+						let used = &b.sets::<S>().used;
+						dlx = Some(Dlx::new(&b.to_grid(), b.hsize, [&used[0][..], &used[1][..], &used[2][..]]));
+					});
+				}
+				SolverBackend::Candidates => {
+					by_width!(b.sets, S => b.update_all_cand::<S>()); //Update the candidates for all cells
+					b.process_of_elimination(); //candidates initialization
+				}
+			}
+		}
+
+		Solutions {
+			b,
+			dlx,
+			guesses: vec![],
+			progress: Progress { resume: false, done },
+		}
+	}
+}
+
+//Iterator over every solution of a board, returned by Board::solutions().
+#[derive(Clone)]
+pub struct Solutions {
+	b: Board, //The board being searched, rewound along its trail on backtracking
+	dlx: Option<Dlx>, //Exact-cover search used instead of b by the DancingLinks backend
+	guesses: Vec<([usize; 3], usize)>, //Each guess as a last_modified entry, with the trail length before it was made
+	progress: Progress,
+}
+impl Solutions {
+
+	//Search the solutions of a branch split off another search, which is already validated and updated.
+	fn from_branch(b: Board) -> Self {
+		Solutions {
+			b,
+			dlx: None,
+			guesses: vec![],
+			progress: Progress::default(),
+		}
+	}
+
+	//Run until the next solution is found, calling on_guess after every guess.
+	fn next_with<F: FnMut(&Board)>(&mut self, on_guess: &mut F) -> Option<Solution> {
+		self.search(&mut |s| {
+			on_guess(&s.b);
+			true
+		})
+	}
+
+	//Main back-tracking loop, run until the next solution is found, calling on_guess after every guess.
+	//The search stops for good once on_guess returns false.
+	pub(crate) fn search<F: FnMut(&mut Solutions) -> bool>(&mut self, on_guess: &mut F) -> Option<Solution> {
+
+		//Fill a copy of the unsolved board with each exact cover.
+		if let Some(dlx) = &mut self.dlx {
+			let cover = dlx.next_cover()?;
+			let mut board = self.b.clone();
+			for (coord, digit) in cover {
+				by_width!(board.sets, S => board.set_digit::<S>(coord, digit));
+			}
+			board.clear_trail();
+			return Some(Solution { board });
+		}
+
+		backtrack::next_solution(self, on_guess)
+	}
+
+	//Guess on an empty cell, returning false if it has no candidates.
+	fn guess<S: Store>(&mut self) -> bool {
+
+		//Pick an empty cell to guess on
+		let coord = self.b.branch_cell::<S>();
+		let cand = &self.b.sets::<S>().cand[coord[0]][coord[1]];
+		let (first, len) = (cand.first(), cand.len());
+
+		//Ensure cell has candidates
+		let Some(c) = first else {
+			return false;
+		};
+
+		//Set cell to first candidate and update the last-modified cell data.
+		self.guesses.push(([coord[0], coord[1], c as usize], self.b.trail.len()));
+		self.b.set_digit::<S>(coord, c);
+		self.b.set_guessed(coord, len == 1);
+		self.b.last_modified = [coord[0], coord[1], c as usize];
+
+		//Update candidates and check for area candidate eliminations.
+		self.b.update_cand::<S>(coord);
+		self.b.process_of_elimination();
+		true
+	}
+
+	//Hand the untried candidates of the earliest guess over to a new search, which this one will no longer visit.
+	pub(crate) fn split_off(&mut self) -> Option<Solutions> {
+		if self.guesses.is_empty() {
+			return None;
+		}
+		let (guess, mark) = self.guesses.remove(0);
+
+		//Rebuild the board as it was before the guess, with the guess ruled out.
+		let mut b = self.b.clone();
+		by_width!(b.sets, S => {
+			b.rewind::<S>(mark);
+			b.limit_cand::<S>([guess[0], guess[1]], guess[2] as u16);
+		});
+		b.last_modified = [0,0,0];
+		b.process_of_elimination();
+		b.clear_trail();
+
+		Some(Solutions::from_branch(b))
+	}
+}
+
+impl Backtrack for Solutions {
+	type Solution = Solution;
+
+	fn progress(&mut self) -> &mut Progress {
+		&mut self.progress
+	}
+
+	//Copy of the solved board, without the trail that produced it
+	fn solution(&self) -> Option<Solution> {
+		if !self.b.is_solved() {
+			return None;
+		}
+		let mut board = self.b.clone();
+		board.clear_trail();
+		Some(Solution { board })
+	}
+
+	fn choose(&mut self) -> bool {
+		by_width!(self.b.sets, S => self.guess::<S>())
+	}
+
+	//Rewind the most recent guess and rule it out, finishing the search once no guesses are left.
+	fn backtrack(&mut self) {
+		let (guess, mark) = match self.guesses.pop() {
+			Some(last) => last,
+			None => {
+				self.progress.done = true;
+				return;
+			}
+		};
+
+		//Revert every change made since the guess, including the guess itself, then update the guessed
+		//cell's cand_limit and check for area candidate eliminations.
+		by_width!(self.b.sets, S => {
+			self.b.rewind::<S>(mark);
+			self.b.limit_cand::<S>([guess[0], guess[1]], guess[2] as u16);
+		});
+		self.b.last_modified = self.guesses.last().map_or([0,0,0], |last| last.0);
+		self.b.process_of_elimination();
+	}
+}
+
+impl Iterator for Solutions {
+	type Item = Solution;
+
+	fn next(&mut self) -> Option<Solution> {
+		self.next_with(&mut |_| {})
+	}
+}
+
+//Plain board output, one row per line, readable by parse::grid.
+impl fmt::Display for Board {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		//How much space each digit needs.
+		let width = self.bsize.to_string().len();
+
+		for i in 0..self.bsize {
+			for j in 0..self.bsize {
+				if j != 0 {
+					write!(f, " ")?;
+				}
+				write!(f, "{:>width$}", self.cell[i][j].digit, width = width)?;
+			}
+			writeln!(f)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::logic::Technique;
+
+	#[test]
+	fn new_board_is_empty() {
+		let b = Board::new(9).unwrap();
+		assert_eq!(b.digit(0, 0), 0);
+		assert_eq!(b.candidates(8, 8), (1..=9).collect::<Vec<u16>>());
+		assert!(!b.is_solved());
+		assert!(b.solve().unwrap().board().is_solved());
+		assert!(matches!(Board::new(0), Err(SolveError::NotSquare(0))));
+		assert!(matches!(Board::new(8), Err(SolveError::NotSquare(8))));
+	}
+
+	#[test]
+	fn reports_bad_grids() {
+		let ragged = vec![vec![1, 2, 3, 4], vec![0, 0, 0], vec![0; 4], vec![0; 4]];
+		assert_eq!(Board::from_grid(&ragged).err(), Some(SolveError::RaggedRows { row: 1, len: 3 }));
+
+		let mut grid = vec![vec![0; 4]; 4];
+		grid[2][3] = 5;
+		assert_eq!(Board::from_grid(&grid).err(), Some(SolveError::DigitOutOfRange { cell: [2, 3], digit: 5 }));
+
+		grid[2][3] = 0;
+		grid[0][1] = 2;
+		grid[3][1] = 2;
+		let b = Board::from_grid(&grid).unwrap();
+		let conflict = SolveError::ConflictingGivens { cell_a: [0, 1], cell_b: [3, 1], digit: 2 };
+		assert_eq!(b.solve().err(), Some(conflict.clone()));
+		assert_eq!(b.solve_parallel(2).err(), Some(conflict));
+	}
+
+	#[test]
+	fn validates_givens() {
+		//Two 1s sharing both the first row and the first house, and givens that leave row 1, column 4 without a candidate
+		let grid = vec![vec![1, 1, 0, 0], vec![0, 0, 2, 0], vec![0, 0, 0, 4], vec![0, 0, 0, 3]];
+		let errors = Board::from_grid(&grid).unwrap().validate().unwrap_err();
+		assert_eq!(errors, vec![
+			SolveError::ConflictingGivens { cell_a: [0, 0], cell_b: [0, 1], digit: 1 },
+			SolveError::NoCandidates { cell: [0, 3] },
+		]);
+
+		let grid = vec![vec![1, 0, 0, 0], vec![0, 0, 2, 0], vec![0, 0, 0, 0], vec![0, 0, 0, 0]];
+		assert_eq!(Board::from_grid(&grid).unwrap().validate(), Ok(()));
+	}
+
+	#[test]
+	fn rewinds_a_guess() {
+		let grid = crate::parse::line("..76.594.........68..1...........2...7..9......9..453..1.5..36......6..7..3.....2").unwrap();
+		let mut b = Board::from_grid(&grid).unwrap();
+		b.update_all_cand::<DigitSet>();
+		b.process_of_elimination();
+		assert!(!b.is_solved());
+		let before = b.clone();
+
+		//Guess like the search does, letting candidate analysis run on from it, then take it all back.
+		let mark = b.trail.len();
+		let coord = b.branch_cell::<DigitSet>();
+		let guess = b.candidate_set(coord[0], coord[1]).first().unwrap();
+		b.set_digit::<DigitSet>(coord, guess);
+		b.set_guessed(coord, false);
+		b.update_cand::<DigitSet>(coord);
+		b.process_of_elimination();
+		assert!(b.empty < before.empty - 1);
+		b.rewind::<DigitSet>(mark);
+
+		assert_eq!(b.to_grid(), before.to_grid());
+		assert_eq!(b.empty, before.empty);
+		assert_eq!(b.trail.len(), mark);
+		let (sets, old) = (b.sets::<DigitSet>(), before.sets::<DigitSet>());
+		assert!(sets.cand == old.cand && sets.cand_limit == old.cand_limit && sets.used == old.used);
+		assert!((0..9).all(|i| (0..9).all(|j| !b.cell[i][j].guessed && !b.cell[i][j].known)));
+	}
+
+	#[test]
+	fn branching_strategies_agree() {
+		let grid = crate::parse::line("..76.594.........68..1...........2...7..9......9..453..1.5..36......6..7..3.....2").unwrap();
+		let mut b = Board::from_grid(&grid).unwrap();
+		b.set_deduction(Deduction::Singles);
+		let solved = b.solve().unwrap().to_grid();
+
+		let last_empty = Branching::custom(|b: &Board| {
+			let n = b.size();
+			(0..n*n).rev().map(|k| [k/n, k%n]).find(|c| b.digit(c[0], c[1]) == 0).unwrap_or([0, 0])
+		});
+		let given = Branching::custom(|_: &Board| [0, 2]); //Always a filled cell, so FewestCandidates is used
+		for branching in [Branching::FirstEmpty, Branching::MostConstrainedUnit, last_empty, given] {
+			b.set_branching(branching);
+			let mut guesses = 0;
+			let solution = b.solve_with(|s| {
+				//Only the cells guessed on are shown as guesses, wherever the strategy picked them.
+				let [i, j, _] = s.last_modified;
+				assert!(s.cell[i][j].guessed);
+				assert!(s.cell.iter().flatten().all(|c| !c.guessed || c.was_empty));
+				guesses += 1;
+			}).unwrap();
+			assert!(guesses > 0);
+			assert_eq!(solution.to_grid(), solved);
+		}
+	}
+
+	#[test]
+	fn solves_in_parallel() {
+		let grid = crate::parse::line("..76.594.........68..1...........2...7..9......9..453..1.5..36......6..7..3.....2").unwrap();
+		let b = Board::from_grid(&grid).unwrap();
+		let solved = b.solve().unwrap().to_grid();
+		for threads in [0, 4] {
+			let solution = b.solve_parallel(threads).unwrap();
+			assert!(solution.board().is_solved());
+			assert_eq!(Board::from_grid(&solution.to_grid()).unwrap().validate(), Ok(()));
+			assert_eq!(solution.to_grid(), solved);
+		}
+
+		//Valid givens, but 1s in the first rows and columns leave the first house nowhere for a 1,
+		//which only shows once the workers have searched every branch.
+		let grid = crate::parse::line("...1...........1....5...............1...........................1................").unwrap();
+		let mut b = Board::from_grid(&grid).unwrap();
+		b.set_deduction(Deduction::Singles);
+		assert_eq!(b.validate(), Ok(()));
+		assert_eq!(b.solve_parallel(4).err(), Some(SolveError::Unsolvable));
+	}
+
+	#[test]
+	fn deduction_levels_agree() {
+		let grid = crate::parse::line("..76.594.........68..1...........2...7..9......9..453..1.5..36......6..7..3.....2").unwrap();
+		let mut b = Board::from_grid(&grid).unwrap();
+		let mut guesses = vec![];
+		let mut solutions = vec![];
+		for deduction in [Deduction::Singles, Deduction::Intersections, Deduction::Subsets, Deduction::Full] {
+			b.set_deduction(deduction);
+			let mut count = 0;
+			solutions.push(b.solve_with(|_| count += 1).unwrap().to_grid());
+			guesses.push(count);
+		}
+		assert!(solutions.iter().all(|s| *s == solutions[0]));
+		assert!(guesses[3] < guesses[0], "{:?}", guesses);
+	}
+
+	#[test]
+	fn rates_puzzles() {
+		let rating = |line: &str| Board::from_grid(&crate::parse::line(line).unwrap()).unwrap().solve_logically().unwrap().rating();
+		assert_eq!(rating(".4587.9.....9.....2.8.6...4.1.2..4..93.5472....469.7.3.6.48..3138.7.26.9.....6.27"), Rating::Singles);
+		assert_eq!(rating("85.....2......2...6...8.31.3..19.2..5..7...6......8...14.625....6.......7.3..9..."),
+			Rating::Technique(Technique::Pointing));
+		assert_eq!(rating("12.3.....4.....3....3.5......42..5......8...9.6...5.7...15..2......9..6......7..8"), Rating::Guessing);
+	}
+
+	#[test]
+	fn solves_logically() {
+		let grid = crate::parse::line("..76.594.........68..1...........2...7..9......9..453..1.5..36......6..7..3.....2").unwrap();
+		let b = Board::from_grid(&grid).unwrap();
+		let solved = b.solve().unwrap().to_grid();
+		let result = b.solve_logically().unwrap();
+
+		assert!(result.is_solved());
+		assert_eq!(result.board().to_grid(), solved);
+		let techniques: Vec<Technique> = result.steps().iter().map(|step| step.technique).collect();
+		assert_eq!(techniques, vec![Technique::Pointing, Technique::HiddenSubset(2)]);
+		assert_eq!(result.rating(), Rating::Technique(Technique::HiddenSubset(2)));
+
+		//No step rules out a digit of the solution.
+		for &([i, j], digit) in result.steps().iter().flat_map(|step| &step.eliminations) {
+			assert_ne!(solved[i][j], digit);
+		}
+	}
+
+	#[test]
+	fn backends_agree() {
+		let empty = Board::new(4).unwrap();
+		let mut dlx = empty.clone();
+		dlx.set_backend(SolverBackend::DancingLinks);
+		assert_eq!(empty.count_solutions(1000), SolutionCount::Exactly(288));
+		assert_eq!(dlx.count_solutions(1000), SolutionCount::Exactly(288));
+
+		//A 9x9 board with its top band emptied, which has several solutions.
+		let grid: Vec<Vec<u16>> = (0..9).map(|i| {
+			(0..9).map(|j| if i < 3 { 0 } else { ((3*(i%3) + i/3 + j) % 9 + 1) as u16 }).collect()
+		}).collect();
+		let mut b = Board::from_grid(&grid).unwrap();
+		let mut found = vec![];
+		for backend in [SolverBackend::Candidates, SolverBackend::DancingLinks] {
+			b.set_backend(backend);
+			let mut solutions: Vec<Vec<Vec<u16>>> = b.solutions().map(|s| s.to_grid()).collect();
+			solutions.sort();
+			found.push(solutions);
+		}
+		assert!(found[0].len() > 1);
+		assert_eq!(found[0], found[1]);
+	}
+
+	#[test]
+	fn solves_wide_boards() {
+		let (bsize, hsize) = (81, 9);
+		let solved: Vec<Vec<u16>> = (0..bsize).map(|i| {
+			(0..bsize).map(|j| ((hsize*(i%hsize) + i/hsize + j) % bsize + 1) as u16).collect()
+		}).collect();
+		let mut grid = solved.clone();
+		for (i, row) in grid.iter_mut().enumerate() {
+			for (j, digit) in row.iter_mut().enumerate() {
+				if (i + j) % hsize == 0 {
+					*digit = 0;
+				}
+			}
+		}
+
+		let mut b = Board::from_grid(&grid).unwrap();
+		assert_eq!(b.candidates(0, 0), vec![1]);
+		assert_eq!(b.solve().unwrap().to_grid(), solved);
+		assert_eq!(b.solve_parallel(4).unwrap().to_grid(), solved);
+		assert_eq!(b.count_solutions(2), SolutionCount::Exactly(1));
+
+		//Options reach wide boards too, instead of a search of their own ignoring them.
+		b.set_deduction(Deduction::Full);
+		for branching in [Branching::FirstEmpty, Branching::MostConstrainedUnit] {
+			b.set_branching(branching);
+			assert_eq!(b.solve().unwrap().to_grid(), solved);
+		}
+		b.set_backend(SolverBackend::DancingLinks);
+		assert_eq!(b.solve().unwrap().to_grid(), solved);
+		assert_eq!(b.count_solutions(2), SolutionCount::Exactly(1));
+		assert!(matches!(b.solve_logically(), Err(SolveError::LogicTooLarge { bsize: 81, max: 64 })));
+	}
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::board::Board;
+
+//Custom branching strategy, returning the empty cell to guess on next.
+pub type BranchFn = dyn Fn(&Board) -> [usize; 2] + Send + Sync;
+
+//How backtracking picks the next empty cell to guess on.
+#[derive(Clone, Default)]
+pub enum Branching {
+	FirstEmpty, //First empty cell in row-major order
+	#[default]
+	FewestCandidates, //Empty cell with the fewest candidates (minimum remaining values)
+	MostConstrainedUnit, //Cell with the fewest candidates in the row, column or house with the fewest empty cells
+	Custom(Arc<BranchFn>), //Any empty cell, falling back to FewestCandidates otherwise
+}
+impl Branching {
+
+	//Wrap a closure as a custom branching strategy.
+	pub fn custom<F: Fn(&Board) -> [usize; 2] + Send + Sync + 'static>(f: F) -> Self {
+		Branching::Custom(Arc::new(f))
+	}
+}
+
+impl fmt::Debug for Branching {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Branching::FirstEmpty => write!(f, "FirstEmpty"),
+			Branching::FewestCandidates => write!(f, "FewestCandidates"),
+			Branching::MostConstrainedUnit => write!(f, "MostConstrainedUnit"),
+			Branching::Custom(_) => write!(f, "Custom"),
+		}
+	}
+}
@@ -0,0 +1,69 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+//Used to allow color printing on Windows consoles.
+#[cfg(windows)]
+pub fn enable_virtual_terminal_processing() -> io::Result<()> {
+	use winapi::um::consoleapi::GetConsoleMode;
+	use winapi::um::consoleapi::SetConsoleMode;
+	use winapi::um::processenv::GetStdHandle;
+	use winapi::um::winbase::STD_OUTPUT_HANDLE;
+	use winapi::um::wincon::ENABLE_VIRTUAL_TERMINAL_PROCESSING;
+
+	unsafe {
+		//Get the handle to the standard output (console)
+		let stdout_handle = GetStdHandle(STD_OUTPUT_HANDLE);
+		if stdout_handle.is_null() {
+			return Err(io::Error::last_os_error());
+		}
+
+		//Get the current console mode
+		let mut mode: u32 = 0;
+		if GetConsoleMode(stdout_handle, &mut mode) == 0 {
+			return Err(io::Error::last_os_error());
+		}
+
+		//Enable ENABLE_VIRTUAL_TERMINAL_PROCESSING flag
+		mode |= ENABLE_VIRTUAL_TERMINAL_PROCESSING;
+		if SetConsoleMode(stdout_handle, mode) == 0 {
+			return Err(io::Error::last_os_error());
+		}
+	}
+
+	Ok(())
+}
+
+//Other terminals understand ANSI escape codes out of the box.
+#[cfg(not(windows))]
+pub fn enable_virtual_terminal_processing() -> io::Result<()> {
+	Ok(())
+}
+
+//Prepare the console for printing boards, disabling colors when stdout is not a terminal.
+pub fn setup() {
+	if !io::stdout().is_terminal() {
+		colored::control::set_override(false);
+		return;
+	}
+
+	if let Err(e) = enable_virtual_terminal_processing() {
+		eprintln!("Error enabling virtual terminal processing: {}", e);
+	}
+}
+
+//Clear the screen, only when stdout is a terminal.
+pub fn clear() {
+	if io::stdout().is_terminal() {
+		print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
+	}
+}
+
+//Wait for the user to press Enter, skipped when stdin is not a terminal.
+pub fn pause() {
+	if !io::stdin().is_terminal() {
+		return;
+	}
+
+	print!("Press Enter to exit...");
+	let _ = io::stdout().flush();
+	let _ = io::stdin().lock().read_line(&mut String::new());
+}
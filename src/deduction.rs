@@ -0,0 +1,10 @@
+//How much candidate analysis backtracking runs after every guess. Each level also runs every level before it.
+//solve_logically() always runs every technique.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Deduction {
+	#[default]
+	Singles, //Naked and hidden singles
+	Intersections, //Locked candidates, by pointing and claiming
+	Subsets, //Naked and hidden pairs, triples and quads
+	Full, //Fish, wings, coloring, chains and almost locked sets, too slow for most searches
+}
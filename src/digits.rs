@@ -0,0 +1,314 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+//Largest board side-length a DigitSet can hold every digit of.
+pub const MAX_SIZE: usize = 64;
+
+//Fixed-width set of digits, where bit d-1 is set when digit d is in the set.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DigitSet(u64);
+impl DigitSet {
+
+	//Set with no digits
+	pub const EMPTY: DigitSet = DigitSet(0);
+
+	//Set with every digit of a board, 1 through bsize
+	pub fn full(bsize: usize) -> Self {
+		if bsize >= MAX_SIZE {
+			DigitSet(u64::MAX)
+		} else {
+			DigitSet((1 << bsize) - 1)
+		}
+	}
+
+	//Set holding a single digit, empty for 0. Digits over MAX_SIZE don't fit, which debug builds catch.
+	pub fn single(digit: u16) -> Self {
+		debug_assert!(digit as usize <= MAX_SIZE, "digit {} does not fit in a DigitSet", digit);
+		if digit == 0 {
+			return DigitSet::EMPTY;
+		}
+		DigitSet(1 << (digit-1))
+	}
+
+	//Whether digit is in the set, which it never is for 0. Digits over MAX_SIZE panic in debug builds.
+	pub fn contains(&self, digit: u16) -> bool {
+		debug_assert!(digit as usize <= MAX_SIZE, "digit {} does not fit in a DigitSet", digit);
+		digit != 0 && self.0 & (1 << (digit-1)) != 0
+	}
+
+	//Add digit to the set, as long as it is at most MAX_SIZE
+	pub fn insert(&mut self, digit: u16) {
+		self.0 |= DigitSet::single(digit).0;
+	}
+
+	pub fn remove(&mut self, digit: u16) {
+		self.0 &= !DigitSet::single(digit).0;
+	}
+
+	//Number of digits in the set
+	pub fn len(&self) -> usize {
+		self.0.count_ones() as usize
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0 == 0
+	}
+
+	//Smallest digit of the set
+	pub fn first(&self) -> Option<u16> {
+		if self.0 == 0 {
+			return None;
+		}
+		Some(self.0.trailing_zeros() as u16 + 1)
+	}
+
+	//Digits of the set in increasing order
+	pub fn iter(&self) -> Digits {
+		Digits(self.0)
+	}
+}
+
+impl BitOr for DigitSet {
+	type Output = DigitSet;
+	fn bitor(self, rhs: DigitSet) -> DigitSet {
+		DigitSet(self.0 | rhs.0)
+	}
+}
+
+impl BitOrAssign for DigitSet {
+	fn bitor_assign(&mut self, rhs: DigitSet) {
+		self.0 |= rhs.0;
+	}
+}
+
+impl BitAnd for DigitSet {
+	type Output = DigitSet;
+	fn bitand(self, rhs: DigitSet) -> DigitSet {
+		DigitSet(self.0 & rhs.0)
+	}
+}
+
+impl BitAndAssign for DigitSet {
+	fn bitand_assign(&mut self, rhs: DigitSet) {
+		self.0 &= rhs.0;
+	}
+}
+
+//Digits of the left set that are not in the right set
+impl Sub for DigitSet {
+	type Output = DigitSet;
+	fn sub(self, rhs: DigitSet) -> DigitSet {
+		DigitSet(self.0 & !rhs.0)
+	}
+}
+
+impl SubAssign for DigitSet {
+	fn sub_assign(&mut self, rhs: DigitSet) {
+		self.0 &= !rhs.0;
+	}
+}
+
+impl FromIterator<u16> for DigitSet {
+	fn from_iter<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+		let mut set = DigitSet::EMPTY;
+		for digit in iter {
+			set.insert(digit);
+		}
+		set
+	}
+}
+
+impl IntoIterator for DigitSet {
+	type Item = u16;
+	type IntoIter = Digits;
+	fn into_iter(self) -> Digits {
+		self.iter()
+	}
+}
+
+impl fmt::Debug for DigitSet {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_set().entries(self.iter()).finish()
+	}
+}
+
+//Iterator over the digits of a DigitSet, returned by DigitSet::iter().
+#[derive(Clone)]
+pub struct Digits(u64);
+
+impl Iterator for Digits {
+	type Item = u16;
+
+	fn next(&mut self) -> Option<u16> {
+		if self.0 == 0 {
+			return None;
+		}
+		let digit = self.0.trailing_zeros() as u16 + 1;
+		self.0 &= self.0 - 1; //Clear the lowest set bit
+		Some(digit)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.0.count_ones() as usize;
+		(len, Some(len))
+	}
+}
+
+//Digit set of a fixed width. The candidate search keeps its sets in DigitSets on boards up to MAX_SIZE and
+//in WideSets on larger boards, through the same code.
+pub(crate) trait DigitBits: Clone + PartialEq + Send + Sync + 'static {
+
+	//Set with no digits, with room for every digit of a board
+	fn empty(bsize: usize) -> Self;
+
+	//Set with every digit of a board, 1 through bsize
+	fn full(bsize: usize) -> Self;
+
+	fn insert(&mut self, digit: u16);
+
+	fn remove(&mut self, digit: u16);
+
+	//Number of digits in the set
+	fn len(&self) -> usize;
+
+	fn is_empty(&self) -> bool;
+
+	//Smallest digit of the set
+	fn first(&self) -> Option<u16>;
+
+	//Digits in either set
+	fn or(&self, other: &Self) -> Self;
+
+	//Digits in both sets
+	fn and(&self, other: &Self) -> Self;
+
+	//Digits of this set that are not in the other
+	fn minus(&self, other: &Self) -> Self;
+
+	//Digits of the set in increasing order
+	fn digits(&self) -> impl Iterator<Item = u16> + '_;
+}
+
+impl DigitBits for DigitSet {
+	fn empty(_: usize) -> Self {
+		DigitSet::EMPTY
+	}
+
+	fn full(bsize: usize) -> Self {
+		DigitSet::full(bsize)
+	}
+
+	fn insert(&mut self, digit: u16) {
+		DigitSet::insert(self, digit);
+	}
+
+	fn remove(&mut self, digit: u16) {
+		DigitSet::remove(self, digit);
+	}
+
+	fn len(&self) -> usize {
+		DigitSet::len(self)
+	}
+
+	fn is_empty(&self) -> bool {
+		DigitSet::is_empty(self)
+	}
+
+	fn first(&self) -> Option<u16> {
+		DigitSet::first(self)
+	}
+
+	fn or(&self, other: &Self) -> Self {
+		*self | *other
+	}
+
+	fn and(&self, other: &Self) -> Self {
+		*self & *other
+	}
+
+	fn minus(&self, other: &Self) -> Self {
+		*self - *other
+	}
+
+	fn digits(&self) -> impl Iterator<Item = u16> + '_ {
+		self.iter()
+	}
+}
+
+//Set of digits for boards too large for a DigitSet, where bit (d-1)%64 of word (d-1)/64 is set when digit d
+//is in the set. Slower than a DigitSet, so only used for boards over MAX_SIZE.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct WideSet(Vec<u64>);
+
+impl DigitBits for WideSet {
+	fn empty(bsize: usize) -> Self {
+		WideSet(vec![0; bsize.div_ceil(64)])
+	}
+
+	fn full(bsize: usize) -> Self {
+		let mut set = WideSet(vec![u64::MAX; bsize.div_ceil(64)]);
+		if !bsize.is_multiple_of(64) {
+			*set.0.last_mut().unwrap() = (1 << (bsize % 64)) - 1;
+		}
+		set
+	}
+
+	fn insert(&mut self, digit: u16) {
+		let bit = digit as usize - 1;
+		self.0[bit/64] |= 1 << (bit%64);
+	}
+
+	fn remove(&mut self, digit: u16) {
+		let bit = digit as usize - 1;
+		self.0[bit/64] &= !(1 << (bit%64));
+	}
+
+	fn len(&self) -> usize {
+		self.0.iter().map(|word| word.count_ones() as usize).sum()
+	}
+
+	fn is_empty(&self) -> bool {
+		self.0.iter().all(|&word| word == 0)
+	}
+
+	fn first(&self) -> Option<u16> {
+		let k = self.0.iter().position(|&word| word != 0)?;
+		Some((k*64) as u16 + self.0[k].trailing_zeros() as u16 + 1)
+	}
+
+	fn or(&self, other: &Self) -> Self {
+		WideSet(self.0.iter().zip(&other.0).map(|(a, b)| a | b).collect())
+	}
+
+	fn and(&self, other: &Self) -> Self {
+		WideSet(self.0.iter().zip(&other.0).map(|(a, b)| a & b).collect())
+	}
+
+	fn minus(&self, other: &Self) -> Self {
+		WideSet(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+	}
+
+	fn digits(&self) -> impl Iterator<Item = u16> + '_ {
+		self.0.iter().enumerate().flat_map(|(k, &word)| Digits(word).map(move |digit| digit + (k*64) as u16))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn holds_digits_up_to_max_size() {
+		let mut set = DigitSet::single(MAX_SIZE as u16);
+		set.insert(1);
+		assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, MAX_SIZE as u16]);
+		assert!(!set.contains(0));
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "does not fit in a DigitSet")]
+	fn rejects_digits_over_max_size() {
+		DigitSet::EMPTY.contains(MAX_SIZE as u16 + 1);
+	}
+}
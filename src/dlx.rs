@@ -0,0 +1,227 @@
+use crate::backtrack::{self, Backtrack, Progress};
+use crate::digits::DigitBits;
+
+//Exact-cover matrix of a board's unsolved constraints, searched with Algorithm X using dancing links.
+//Every empty cell needs a digit and every row, column and house needs each of its missing digits, so
+//a matrix row is one digit placed in one cell, covering its cell, row-digit, column-digit and house-digit columns.
+#[derive(Clone)]
+pub(crate) struct Dlx {
+	left: Vec<usize>, //Node to the left, wrapping around its row
+	right: Vec<usize>, //Node to the right, wrapping around its row
+	up: Vec<usize>, //Node above, wrapping around its column
+	down: Vec<usize>, //Node below, wrapping around its column
+	col: Vec<usize>, //Column header of each node
+	size: Vec<usize>, //Number of nodes in each column, indexed by header
+	row: Vec<usize>, //Matrix row of each node
+	placements: Vec<([usize; 2], u16)>, //Cell and digit of each matrix row
+	stack: Vec<usize>, //Node of each chosen matrix row, most recent last
+	progress: Progress,
+}
+impl Dlx {
+
+	//Build the matrix of a board, where used holds the digits placed in each row, column and house, in sets of any width.
+	//Node 0 is the root, followed by one header per unsolved constraint.
+	pub(crate) fn new<S: DigitBits>(grid: &[Vec<u16>], hsize: usize, used: [&[S]; 3]) -> Self {
+		let bsize = grid.len();
+		let area = bsize*bsize;
+		let [row_used, col_used, house_used] = used;
+
+		let mut dlx = Self {
+			left: vec![0],
+			right: vec![0],
+			up: vec![0],
+			down: vec![0],
+			col: vec![0],
+			size: vec![0],
+			row: vec![0],
+			placements: vec![],
+			stack: vec![],
+			progress: Progress::default(),
+		};
+
+		//Header of every constraint still to be covered, 0 for those the givens already satisfy.
+		let mut header = vec![0; 4*area];
+		for i in 0..bsize {
+			for j in 0..bsize {
+				if grid[i][j] == 0 {
+					header[i*bsize + j] = dlx.add_header();
+				}
+			}
+		}
+		for (u, used) in [row_used, col_used, house_used].into_iter().enumerate() {
+			for unit in 0..bsize {
+				for d in S::full(bsize).minus(&used[unit]).digits() {
+					header[(u+1)*area + unit*bsize + d as usize - 1] = dlx.add_header();
+				}
+			}
+		}
+
+		//One matrix row per digit that is free in an empty cell's row, column and house.
+		for i in 0..bsize {
+			for j in 0..bsize {
+				if grid[i][j] != 0 {
+					continue;
+				}
+
+				let h = (i/hsize)*hsize + j/hsize;
+				let free = S::full(bsize).minus(&row_used[i]).minus(&col_used[j]).minus(&house_used[h]);
+				for d in free.digits() {
+					let k = d as usize - 1;
+					dlx.add_row([i, j], d, [
+						header[i*bsize + j],
+						header[area + i*bsize + k],
+						header[2*area + j*bsize + k],
+						header[3*area + h*bsize + k],
+					]);
+				}
+			}
+		}
+
+		dlx
+	}
+
+	//Append a column header to the right of the last one.
+	fn add_header(&mut self) -> usize {
+		let c = self.left.len();
+		self.left.push(self.left[0]);
+		self.right.push(0);
+		self.right[self.left[0]] = c;
+		self.left[0] = c;
+		self.up.push(c);
+		self.down.push(c);
+		self.col.push(c);
+		self.size.push(0);
+		self.row.push(0);
+		c
+	}
+
+	//Append a matrix row with one node in each of its columns.
+	fn add_row(&mut self, coord: [usize; 2], digit: u16, cols: [usize; 4]) {
+		let r = self.placements.len();
+		let first = self.left.len();
+		self.placements.push((coord, digit));
+
+		for (k, &c) in cols.iter().enumerate() {
+			let n = first + k;
+			self.left.push(if k == 0 { first + 3 } else { n - 1 });
+			self.right.push(if k == 3 { first } else { n + 1 });
+			self.up.push(self.up[c]);
+			self.down.push(c);
+			self.down[self.up[c]] = n;
+			self.up[c] = n;
+			self.col.push(c);
+			self.row.push(r);
+			self.size[c] += 1;
+		}
+	}
+
+	//Remove a column from the header list and every row crossing it from the other columns.
+	fn cover(&mut self, c: usize) {
+		self.right[self.left[c]] = self.right[c];
+		self.left[self.right[c]] = self.left[c];
+
+		let mut i = self.down[c];
+		while i != c {
+			let mut j = self.right[i];
+			while j != i {
+				self.down[self.up[j]] = self.down[j];
+				self.up[self.down[j]] = self.up[j];
+				self.size[self.col[j]] -= 1;
+				j = self.right[j];
+			}
+			i = self.down[i];
+		}
+	}
+
+	//Undo cover(c), in exactly the reverse order.
+	fn uncover(&mut self, c: usize) {
+		let mut i = self.up[c];
+		while i != c {
+			let mut j = self.left[i];
+			while j != i {
+				self.size[self.col[j]] += 1;
+				self.down[self.up[j]] = j;
+				self.up[self.down[j]] = j;
+				j = self.left[j];
+			}
+			i = self.up[i];
+		}
+
+		self.right[self.left[c]] = c;
+		self.left[self.right[c]] = c;
+	}
+
+	//Choose the matrix row of node r, covering the other columns it satisfies.
+	fn select(&mut self, r: usize) {
+		let mut j = self.right[r];
+		while j != r {
+			self.cover(self.col[j]);
+			j = self.right[j];
+		}
+		self.stack.push(r);
+	}
+
+	//Undo the most recent select(), returning its node.
+	fn unselect(&mut self) -> Option<usize> {
+		let r = self.stack.pop()?;
+		let mut j = self.left[r];
+		while j != r {
+			self.uncover(self.col[j]);
+			j = self.left[j];
+		}
+		Some(r)
+	}
+
+	//Run the search until the next exact cover, returned as the cell and digit of every placement.
+	pub(crate) fn next_cover(&mut self) -> Option<Vec<([usize; 2], u16)>> {
+		backtrack::next_solution(self, &mut |_| true)
+	}
+}
+
+impl Backtrack for Dlx {
+	type Solution = Vec<([usize; 2], u16)>;
+
+	fn progress(&mut self) -> &mut Progress {
+		&mut self.progress
+	}
+
+	//Every constraint is covered
+	fn solution(&self) -> Option<Self::Solution> {
+		if self.right[0] != 0 {
+			return None;
+		}
+		Some(self.stack.iter().map(|&r| self.placements[self.row[r]]).collect())
+	}
+
+	//Branch on the column with the fewest rows, a constraint nothing can satisfy being a dead end.
+	fn choose(&mut self) -> bool {
+		let mut c = self.right[0];
+		let mut j = self.right[c];
+		while j != 0 && self.size[c] > 0 {
+			if self.size[j] < self.size[c] {
+				c = j;
+			}
+			j = self.right[j];
+		}
+
+		if self.size[c] == 0 {
+			return false;
+		}
+		self.cover(c);
+		self.select(self.down[c]);
+		true
+	}
+
+	//Move the most recent choice on to the next row of its column, backing out of exhausted columns.
+	fn backtrack(&mut self) {
+		while let Some(r) = self.unselect() {
+			let c = self.col[r];
+			if self.down[r] != c {
+				self.select(self.down[r]);
+				return;
+			}
+			self.uncover(c);
+		}
+		self.progress.done = true;
+	}
+}
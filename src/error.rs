@@ -0,0 +1,66 @@
+use std::fmt;
+
+//Reasons a board can fail to be built or solved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+	NotSquare(usize), //Board side-length is not a square number, so houses can't be formed
+	TooLarge { bsize: usize, max: usize }, //Board side-length is larger than max, the largest supported
+	LogicTooLarge { bsize: usize, max: usize }, //Board side-length is larger than max, the largest solve_logically supports
+	RaggedRows { row: usize, len: usize }, //A row's length differs from the number of rows
+	DigitOutOfRange { cell: [usize; 2], digit: u16 }, //A given is larger than the board side-length
+	ConflictingGivens { cell_a: [usize; 2], cell_b: [usize; 2], digit: u16 }, //Two givens share a row, column or house
+	NoCandidates { cell: [usize; 2] }, //An empty cell that no digit can go in
+	Unsolvable, //Every branch of the search was exhausted
+}
+
+impl fmt::Display for SolveError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SolveError::NotSquare(bsize) => write!(f, "Board side-length {} is not a square number", bsize),
+			SolveError::TooLarge { bsize, max } => write!(f, "Board side-length {} is larger than the supported {}", bsize, max),
+			SolveError::LogicTooLarge { bsize, max } => write!(f, "Board side-length {} is larger than the {} logical solving supports", bsize, max),
+			SolveError::RaggedRows { row, len } => write!(f, "Row {} has {} cells, which differs from the number of rows", row+1, len),
+			SolveError::DigitOutOfRange { cell, digit } => write!(f, "Digit {} at row {}, column {} is out of range", digit, cell[0]+1, cell[1]+1),
+			SolveError::ConflictingGivens { cell_a, cell_b, digit } => write!(f, "Digit {} appears at both row {}, column {} and row {}, column {}",
+				digit, cell_a[0]+1, cell_a[1]+1, cell_b[0]+1, cell_b[1]+1),
+			SolveError::NoCandidates { cell } => write!(f, "Empty cell at row {}, column {} has no candidates", cell[0]+1, cell[1]+1),
+			SolveError::Unsolvable => write!(f, "Sudoku board has no solution"),
+		}
+	}
+}
+
+impl std::error::Error for SolveError {}
+
+//Reasons a board can fail to be read from text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+	line: usize, //Line the error occurred on, 0 if not tied to a line
+	message: String,
+}
+impl ParseError {
+
+	//Constructor
+	pub fn new(line: usize, message: String) -> Self {
+		Self {
+			line,
+			message,
+		}
+	}
+
+	//Line the error occurred on, 0 if not tied to a line
+	pub fn line(&self) -> usize {
+		self.line
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.line == 0 {
+			write!(f, "{}", self.message)
+		} else {
+			write!(f, "line {}: {}", self.line, self.message)
+		}
+	}
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,29 @@
+/*
+Pseudokude is a dynamic sudoku solver that can solve boards of any square size up to a u16.
+It solves using stack-based backtracking as well as candidate analysis.
+
+Both binaries, src/main.rs and src/main_timed.rs, are built on top of this library.
+*/
+
+mod backend;
+mod backtrack;
+pub mod batch;
+mod board;
+mod branching;
+mod deduction;
+pub mod console;
+mod digits;
+mod dlx;
+mod error;
+mod logic;
+mod parallel;
+pub mod parse;
+mod topology;
+
+pub use backend::SolverBackend;
+pub use board::{Board, LogicalSolution, Solution, SolutionCount, Solutions};
+pub use branching::{BranchFn, Branching};
+pub use deduction::Deduction;
+pub use digits::{DigitSet, Digits, MAX_SIZE};
+pub use error::{ParseError, SolveError};
+pub use logic::{Rating, Step, Technique};
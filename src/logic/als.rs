@@ -0,0 +1,278 @@
+use std::collections::HashSet;
+
+use crate::board::Board;
+use crate::digits::DigitSet;
+use crate::logic::{sees, Step, Technique};
+
+//An almost locked set, n empty cells of one unit holding n+1 digits between them. Losing any one of its digits
+//would lock the rest into its cells.
+struct Als {
+	cells: Vec<[usize; 2]>,
+	digits: DigitSet,
+}
+impl Als {
+
+	//Cells of the set holding a digit
+	fn holding(&self, b: &Board, digit: u16) -> Vec<[usize; 2]> {
+		self.cells.iter().copied().filter(|each| b.candidate_set(each[0], each[1]).contains(digit)).collect()
+	}
+
+	fn overlaps(&self, other: &Als) -> bool {
+		self.cells.iter().any(|each| other.cells.contains(each))
+	}
+}
+
+//Every almost locked set of up to max cells, each listed once even when it lies in several units
+fn find_all(b: &Board, max: usize) -> Vec<Als> {
+	let mut found = vec![];
+	let mut seen: HashSet<Vec<[usize; 2]>> = HashSet::new();
+
+	for unit in &b.topology().units {
+		let empty: Vec<[usize; 2]> = unit.iter().copied().filter(|each| !b.candidate_set(each[0], each[1]).is_empty()).collect();
+		let mut chosen = vec![];
+		extend(b, &empty, max, 0, DigitSet::EMPTY, &mut chosen, &mut |cells, digits| {
+			let mut cells = cells.to_vec();
+			cells.sort_unstable();
+			if seen.insert(cells.clone()) {
+				found.push(Als { cells, digits });
+			}
+		});
+	}
+	found
+}
+
+//Grow a set of cells with cells from index start onwards, backing out once it holds more digits than an
+//almost locked set of max cells could.
+fn extend<F: FnMut(&[[usize; 2]], DigitSet)>(b: &Board, empty: &[[usize; 2]], max: usize, start: usize,
+	digits: DigitSet, chosen: &mut Vec<[usize; 2]>, found: &mut F) {
+	if !chosen.is_empty() && digits.len() == chosen.len() + 1 {
+		found(chosen, digits);
+	}
+	if chosen.len() == max {
+		return;
+	}
+
+	for k in start..empty.len() {
+		let next = digits | b.candidate_set(empty[k][0], empty[k][1]);
+		if next.len() > max + 1 {
+			continue;
+		}
+
+		chosen.push(empty[k]);
+		extend(b, empty, max, k+1, next, chosen, found);
+		chosen.pop();
+	}
+}
+
+//Digits two separate sets share where every cell holding it in one sees every cell holding it in the other.
+//At most one of the two sets can get such a digit.
+fn restricted(b: &Board, a: &Als, c: &Als) -> DigitSet {
+	if a.overlaps(c) {
+		return DigitSet::EMPTY;
+	}
+
+	(a.digits & c.digits).iter().filter(|&d| {
+		let far = c.holding(b, d);
+		a.holding(b, d).iter().all(|&x| far.iter().all(|&y| sees(b, x, y)))
+	}).collect()
+}
+
+//Try ALS-XZ, then ALS-XY-Wing, then Death Blossom.
+pub(crate) fn als(b: &Board) -> Option<Step> {
+	let sets = find_all(b, b.size()/2);
+
+	//Restricted common digits of every pair of sets that have any
+	let mut links: Vec<Vec<(usize, DigitSet)>> = vec![vec![]; sets.len()];
+	for i in 0..sets.len() {
+		for j in i+1..sets.len() {
+			let rcc = restricted(b, &sets[i], &sets[j]);
+			if !rcc.is_empty() {
+				links[i].push((j, rcc));
+				links[j].push((i, rcc));
+			}
+		}
+	}
+
+	als_xz(b, &sets, &links)
+		.or_else(|| als_xy_wing(b, &sets, &links))
+		.or_else(|| death_blossom(b, &sets))
+}
+
+//Two sets share a restricted digit x, so one of them loses it and locks. Any other digit z they share then goes
+//in one of them, and can go in no cell that sees every cell holding z in both.
+fn als_xz(b: &Board, sets: &[Als], links: &[Vec<(usize, DigitSet)>]) -> Option<Step> {
+	for (i, linked) in links.iter().enumerate() {
+		for &(j, rcc) in linked.iter().filter(|&&(j, _)| j > i) {
+			for x in rcc {
+				let shared = (sets[i].digits & sets[j].digits) - DigitSet::single(x);
+				let step = eliminate(b, Technique::AlsXz, shared, &[&sets[i], &sets[j]]);
+				if step.is_some() {
+					return step;
+				}
+			}
+		}
+	}
+	None
+}
+
+//A pivot set shares a restricted digit x with one set and another, y, with a second. The pivot cannot lose both,
+//so one of the other two locks, and any digit z they share can go in no cell that sees every cell holding it.
+fn als_xy_wing(b: &Board, sets: &[Als], links: &[Vec<(usize, DigitSet)>]) -> Option<Step> {
+	for linked in links {
+		for (k, &(i, rx)) in linked.iter().enumerate() {
+			for &(j, ry) in &linked[k+1..] {
+				if sets[i].overlaps(&sets[j]) || (rx | ry).len() < 2 {
+					continue;
+				}
+
+				for x in rx {
+					for y in ry - DigitSet::single(x) {
+						let shared = (sets[i].digits & sets[j].digits) - DigitSet::single(x) - DigitSet::single(y);
+						let step = eliminate(b, Technique::AlsXyWing, shared, &[&sets[i], &sets[j]]);
+						if step.is_some() {
+							return step;
+						}
+					}
+				}
+			}
+		}
+	}
+	None
+}
+
+//A stem cell has a petal set for each of its digits, holding that digit only in cells that see the stem.
+//Whichever digit the stem gets, its petal locks, so a digit z that every petal holds and the stem does not
+//can go in no cell that sees every cell holding z in the petals.
+fn death_blossom(b: &Board, sets: &[Als]) -> Option<Step> {
+	let bsize = b.size();
+
+	for i in 0..bsize {
+		for j in 0..bsize {
+			let stem = b.candidate_set(i, j);
+			if stem.len() < 2 {
+				continue;
+			}
+
+			//Sets that could be the petal of each digit of the stem
+			let petals: Vec<Vec<usize>> = stem.iter().map(|d| {
+				(0..sets.len()).filter(|&k| {
+					let holding = sets[k].holding(b, d);
+					!holding.is_empty() && !sets[k].cells.contains(&[i, j]) && holding.iter().all(|&c| sees(b, c, [i, j]))
+				}).collect()
+			}).collect();
+
+			let mut chosen = vec![];
+			let step = blossom(b, sets, &petals, DigitSet::full(bsize) - stem, &mut chosen);
+			if step.is_some() {
+				return step;
+			}
+		}
+	}
+	None
+}
+
+//Pick a separate petal for each remaining digit of the stem, keeping the digits every petal so far holds.
+fn blossom(b: &Board, sets: &[Als], petals: &[Vec<usize>], shared: DigitSet, chosen: &mut Vec<usize>) -> Option<Step> {
+	if chosen.len() == petals.len() {
+		let chosen: Vec<&Als> = chosen.iter().map(|&k| &sets[k]).collect();
+		return eliminate(b, Technique::DeathBlossom, shared, &chosen);
+	}
+
+	for &k in &petals[chosen.len()] {
+		let next = shared & sets[k].digits;
+		if next.is_empty() || chosen.iter().any(|&c| sets[c].overlaps(&sets[k])) {
+			continue;
+		}
+
+		chosen.push(k);
+		let step = blossom(b, sets, petals, next, chosen);
+		chosen.pop();
+		if step.is_some() {
+			return step;
+		}
+	}
+	None
+}
+
+//Step removing each of some digits from every cell that sees all cells holding it in the sets, where one of
+//the sets is sure to get it.
+fn eliminate(b: &Board, technique: Technique, digits: DigitSet, sets: &[&Als]) -> Option<Step> {
+	let mut eliminations = vec![];
+	for z in digits {
+		let holding: Vec<[usize; 2]> = sets.iter().flat_map(|set| set.holding(b, z)).collect();
+		if holding.is_empty() {
+			continue;
+		}
+
+		for &each in &b.topology().peers[holding[0][0]][holding[0][1]].aoe {
+			if holding.iter().all(|&c| sees(b, each, c)) && b.candidate_set(each[0], each[1]).contains(z) {
+				eliminations.push((each, z));
+			}
+		}
+	}
+
+	Step::new(technique, eliminations)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_als_xz() {
+		let mut b = Board::from_pencilmarks("
+			38   1    4    36  79    5     3678 279 2367
+			35   3567 9    8   367   2     1    47  3467
+			2    3678 367  13  4     1379  3678 79  5
+			4    39   8    5   1379  1379  2    6   137
+			7    2    136  136 1368  1348  5    14  9
+			1359 3569 1356 2   13679 13479 37   8   1347
+			1358 4    2    9   1358  138   67   157 167
+			1359 359  135  7   1235  6     4    125 8
+			6    578  157  4   1258  18    9    3   12
+		");
+		let step = als(&b).unwrap();
+		assert_eq!(step.to_string(), "ALS-XZ: r2c9<>7, r3c7<>7");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(1, 8), vec![3, 4, 6]);
+		assert_eq!(b.candidates(2, 6), vec![3, 6, 8]);
+	}
+
+	#[test]
+	fn finds_als_xy_wing() {
+		let mut b = Board::from_pencilmarks("
+			5    9    148  3   6    48  148 2   7
+			3478 348  6    78  1    2   348 9   5
+			78   2    1348 5   478  9   6   138 34
+			38   6    5    28  238  1   7   4   9
+			2    134  34   9   3457 347 135 6   8
+			9    1348 7    48  3458 6   135 13  2
+			34   7    2    6   9    5   348 38  1
+			6    5    9    1   348  38  2   7   34
+			1    348  348  247 2347 347 9   5   6
+		");
+		let step = als(&b).unwrap();
+		assert_eq!(step.to_string(), "ALS-XY-Wing: r9c5<>3");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(8, 4), vec![2, 4, 7]);
+	}
+
+	#[test]
+	fn finds_death_blossom() {
+		let mut b = Board::from_pencilmarks("
+			8 5 7  3 46 1  49   2   469
+			4 3 1  9 67 2  5    78  678
+			6 9 2  5 8  47 3    1   47
+			3 7 48 1 9  6  2    48  5
+			5 2 48 7 34 34 1489 6   13489
+			9 1 6  2 5  8  47   347 347
+			1 4 9  6 2  5  78   378 378
+			2 6 5  8 37 37 147  9   1347
+			7 8 3  4 1  9  6    5   2
+		");
+		let step = death_blossom(&b, &find_all(&b, b.size()/2)).unwrap();
+		assert_eq!(step.to_string(), "Death Blossom: r8c9<>7");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(7, 8), vec![1, 3, 4]);
+	}
+}
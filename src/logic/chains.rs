@@ -0,0 +1,363 @@
+use std::collections::VecDeque;
+
+use crate::board::Board;
+use crate::digits::DigitSet;
+use crate::logic::{sees, Step, Technique};
+
+//Every candidate of a board as a node, where a strong link means at least one of its two nodes is true and
+//a weak link means at most one is. Bivalue cells and conjugate pairs give strong links, while candidates
+//sharing a cell, or a digit in cells that see each other, give weak links.
+struct Links {
+	nodes: Vec<([usize; 2], u16)>, //Cell and digit of each node
+	index: Vec<usize>, //Node of each cell and digit, usize::MAX where the cell lacks the digit
+	strong: Vec<Vec<usize>>, //Strong links of each node
+	bivalue: Vec<bool>, //Whether each node's cell holds only two candidates
+}
+impl Links {
+
+	//Build the nodes and strong links of a board.
+	fn new(b: &Board) -> Self {
+		let bsize = b.size();
+		let mut nodes = vec![];
+		let mut index = vec![usize::MAX; bsize*bsize*bsize];
+		let mut bivalue = vec![];
+
+		for i in 0..bsize {
+			for j in 0..bsize {
+				let cand = b.candidate_set(i, j);
+				for d in cand {
+					index[(i*bsize + j)*bsize + d as usize - 1] = nodes.len();
+					nodes.push(([i, j], d));
+					bivalue.push(cand.len() == 2);
+				}
+			}
+		}
+
+		let mut links = Self { nodes, index, strong: vec![], bivalue };
+		links.strong = vec![vec![]; links.nodes.len()];
+
+		//Bivalue cells
+		for k in 0..links.nodes.len() {
+			let (cell, d) = links.nodes[k];
+			if links.bivalue[k] {
+				let other = (b.candidate_set(cell[0], cell[1]) - DigitSet::single(d)).first().unwrap();
+				let partner = links.node(b, cell, other);
+				links.strong[k].push(partner);
+			}
+		}
+
+		//Conjugate pairs
+		for unit in &b.topology().units {
+			for d in 1..=bsize as u16 {
+				let places: Vec<usize> = unit.iter().map(|&each| links.node(b, each, d)).filter(|&k| k != usize::MAX).collect();
+				if places.len() == 2 && !links.strong[places[0]].contains(&places[1]) {
+					links.strong[places[0]].push(places[1]);
+					links.strong[places[1]].push(places[0]);
+				}
+			}
+		}
+
+		links
+	}
+
+	//Node of a cell and digit
+	fn node(&self, b: &Board, cell: [usize; 2], digit: u16) -> usize {
+		let bsize = b.size();
+		self.index[(cell[0]*bsize + cell[1])*bsize + digit as usize - 1]
+	}
+
+	//Nodes weakly linked to a node, restricted to other cells when xy is set.
+	fn weak(&self, b: &Board, k: usize, xy: bool) -> Vec<usize> {
+		let (cell, d) = self.nodes[k];
+		let mut weak = vec![];
+
+		if !xy {
+			for other in b.candidate_set(cell[0], cell[1]) - DigitSet::single(d) {
+				weak.push(self.node(b, cell, other));
+			}
+		}
+		for &peer in &b.topology().peers[cell[0]][cell[1]].aoe {
+			let next = self.node(b, peer, d);
+			if next != usize::MAX && (!xy || self.bivalue[next]) {
+				weak.push(next);
+			}
+		}
+		weak
+	}
+
+	//Nodes strongly linked to a node, restricted to its bivalue partner when xy is set.
+	fn strong(&self, k: usize, xy: bool) -> Vec<usize> {
+		self.strong[k].iter().copied().filter(|&next| !xy || self.nodes[next].0 == self.nodes[k].0).collect()
+	}
+
+	//Follow alternating links out of start, assuming it is false, or true when assume is set, until an
+	//elimination turns up. With xy set, only XY-chains through bivalue cells are followed.
+	fn search(&self, b: &Board, start: usize, assume: bool, xy: bool) -> Option<Step> {
+		//State k*2+1 means node k is true if the assumption holds, k*2 that it is false.
+		let mut parent = vec![usize::MAX; 2*self.nodes.len()];
+		let mut queue: VecDeque<usize> = VecDeque::new();
+		let first = start*2 + assume as usize;
+		parent[first] = first;
+
+		//Step off the start, strongly if it is false and weakly if it is true.
+		let next_states: Vec<usize> = if assume {
+			self.weak(b, start, xy).iter().map(|next| next*2).collect()
+		} else {
+			self.strong(start, xy).iter().map(|next| next*2 + 1).collect()
+		};
+		for next in next_states {
+			if parent[next] == usize::MAX {
+				parent[next] = first;
+				queue.push_back(next);
+			}
+		}
+
+		while let Some(state) = queue.pop_front() {
+			let k = state/2;
+
+			if state % 2 == 1 {
+				let weak = self.weak(b, k, xy);
+				if !assume {
+					//A loop back to the start rules out everything the chain alone would, and more.
+					if !xy && weak.contains(&start) {
+						let step = self.continuous_loop(b, &parent, state);
+						if step.is_some() {
+							return step;
+						}
+					}
+
+					let step = if k == start {
+						self.start_is(b, start, true)
+					} else {
+						self.either(b, start, k, xy)
+					};
+					if step.is_some() {
+						return step;
+					}
+				}
+
+				for next in weak {
+					if parent[next*2] == usize::MAX {
+						parent[next*2] = state;
+						queue.push_back(next*2);
+					}
+				}
+			} else {
+				if assume && k == start {
+					let step = self.start_is(b, start, false);
+					if step.is_some() {
+						return step;
+					}
+				}
+
+				for next in self.strong(k, xy) {
+					if parent[next*2 + 1] == usize::MAX {
+						parent[next*2 + 1] = state;
+						queue.push_back(next*2 + 1);
+					}
+				}
+			}
+		}
+		None
+	}
+
+	//A chain that contradicts its own assumption about start proves the opposite.
+	fn start_is(&self, b: &Board, start: usize, value: bool) -> Option<Step> {
+		let (cell, d) = self.nodes[start];
+		let eliminations = if value {
+			(b.candidate_set(cell[0], cell[1]) - DigitSet::single(d)).iter().map(|other| (cell, other)).collect()
+		} else {
+			vec![(cell, d)]
+		};
+		Step::new(Technique::DiscontinuousNiceLoop, eliminations)
+	}
+
+	//A chain from a false start to a true end means at least one of the two ends is true.
+	fn either(&self, b: &Board, start: usize, end: usize, xy: bool) -> Option<Step> {
+		let (c1, x) = self.nodes[start];
+		let (c2, y) = self.nodes[end];
+		let mut eliminations = vec![];
+
+		if x == y {
+			//Same digit in two cells, ruled out of every cell that sees both
+			for &each in &b.topology().peers[c1[0]][c1[1]].aoe {
+				if each != c2 && sees(b, each, c2) && b.candidate_set(each[0], each[1]).contains(x) {
+					eliminations.push((each, x));
+				}
+			}
+			return Step::new(if xy { Technique::XYChain } else { Technique::Aic }, eliminations);
+		}
+		if xy {
+			return None;
+		}
+
+		if c1 == c2 {
+			//Two digits of one cell, so the cell holds one of them
+			for other in b.candidate_set(c1[0], c1[1]) - DigitSet::single(x) - DigitSet::single(y) {
+				eliminations.push((c1, other));
+			}
+		} else if sees(b, c1, c2) {
+			//Different digits in cells that see each other, each ruled out of the other cell
+			if b.candidate_set(c2[0], c2[1]).contains(x) {
+				eliminations.push((c2, x));
+			}
+			if b.candidate_set(c1[0], c1[1]).contains(y) {
+				eliminations.push((c1, y));
+			}
+		}
+		Step::new(Technique::Aic, eliminations)
+	}
+
+	//A chain from a false start that ends on a node weakly linked back to it forms a loop, where every weak
+	//link holds exactly one true node. Each cell of a weak link within a cell holds one of its two digits,
+	//and each other weak link's digit can go in no cell that sees both of its ends.
+	fn continuous_loop(&self, b: &Board, parent: &[usize], end: usize) -> Option<Step> {
+		let mut path = vec![end];
+		while parent[*path.last().unwrap()] != *path.last().unwrap() {
+			path.push(parent[*path.last().unwrap()]);
+		}
+		path.reverse();
+
+		let mut loop_nodes: Vec<usize> = path.iter().map(|state| state/2).collect();
+		loop_nodes.sort_unstable();
+		loop_nodes.dedup();
+		if loop_nodes.len() != path.len() || path.len() < 4 {
+			return None;
+		}
+
+		//Weak links run from each true node to the next node, closing back to the start.
+		let mut eliminations = vec![];
+		for (k, &state) in path.iter().enumerate() {
+			if state % 2 == 0 {
+				continue;
+			}
+
+			let (u, v) = (self.nodes[state/2], self.nodes[path[(k+1) % path.len()]/2]);
+			let removed: Vec<([usize; 2], u16)> = if u.0 == v.0 {
+				(b.candidate_set(u.0[0], u.0[1]) - DigitSet::single(u.1) - DigitSet::single(v.1)).iter().map(|d| (u.0, d)).collect()
+			} else {
+				b.topology().peers[u.0[0]][u.0[1]].aoe.iter()
+					.filter(|&&each| each != v.0 && sees(b, each, v.0) && b.candidate_set(each[0], each[1]).contains(u.1))
+					.map(|&each| (each, u.1))
+					.collect()
+			};
+
+			for (cell, d) in removed {
+				let node = self.node(b, cell, d);
+				if loop_nodes.binary_search(&node).is_err() && !eliminations.contains(&(cell, d)) {
+					eliminations.push((cell, d));
+				}
+			}
+		}
+		Step::new(Technique::ContinuousNiceLoop, eliminations)
+	}
+}
+
+//Try XY-chains, then alternating inference chains and nice loops through every kind of link.
+pub(crate) fn chains(b: &Board) -> Option<Step> {
+	let links = Links::new(b);
+	let starts: Vec<usize> = (0..links.nodes.len()).filter(|&k| !links.strong[k].is_empty()).collect();
+
+	for &start in &starts {
+		if links.bivalue[start] {
+			if let Some(step) = links.search(b, start, false, true) {
+				return Some(step);
+			}
+		}
+	}
+	for &start in &starts {
+		if let Some(step) = links.search(b, start, false, false) {
+			return Some(step);
+		}
+	}
+	for start in 0..links.nodes.len() {
+		if let Some(step) = links.search(b, start, true, false) {
+			return Some(step);
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_xy_chain() {
+		let mut b = Board::from_pencilmarks("
+			4 2 37 578 679 3569 18  169 136
+			6 8 1  2   39  4    35  59  7
+			9 5 37 78  1   36   248 246 2346
+			1 4 2  39  5   39   6   7   8
+			7 6 8  4   2   1    9   3   5
+			3 9 5  6   8   7    124 124 124
+			2 3 9  57  467 56   147 8   146
+			5 7 6  1   34  8    234 24  9
+			8 1 4  39  679 2    57  56  36
+		");
+		let step = chains(&b).unwrap();
+		assert_eq!(step.to_string(), "XY-Chain: r3c4<>7");
+		b.apply_step(&step);
+		//r3c4 is left with its 8, which is placed.
+		assert_eq!(b.digit(2, 3), 8);
+		assert_eq!(b.candidates(0, 3), vec![5, 7]);
+	}
+
+	#[test]
+	fn finds_aic() {
+		let mut b = Board::from_pencilmarks("
+			459  12459 6    19  1249 7    458  3458 235
+			478  3     1478 5   124  6    9    47   27
+			4579 24579 47   8   3    249  457  6    1
+			789  1789  2    4   5    3    6    17   79
+			4569 14569 14   7   28   28   145  1345 359
+			3457 457   347  6   19   19   457  2    8
+			1    478   9    2   478  458  3    578  6
+			2    478   3478 13  6    1458 1578 9    57
+			3678 678   5    139 1789 189  2    178  4
+		");
+		let step = chains(&b).unwrap();
+		assert_eq!(step.to_string(), "AIC: r4c2<>1");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(3, 1), vec![7, 8, 9]);
+	}
+
+	#[test]
+	fn finds_discontinuous_nice_loop() {
+		let mut b = Board::from_pencilmarks("
+			49  3    7  12   124 8    259 6   25
+			89  248  1  256  256 46   7   289 3
+			6   28   5  7    3   9    18  128 4
+			1   68   26 3    9   5    4   7   28
+			5   9    23 4    8   7    123 12  6
+			378 78   4  126  126 16   389 5   89
+			37  4567 8  1569 156 1346 259 249 2579
+			47  1    69 5689 456 2    589 3   78
+			2   45   39 589  7   34   6   489 1
+		");
+		let step = chains(&b).unwrap();
+		assert_eq!(step.to_string(), "Discontinuous Nice Loop: r1c1<>4");
+		b.apply_step(&step);
+		assert_eq!(b.digit(0, 0), 9);
+		assert_eq!(b.validate(), Ok(()));
+	}
+	#[test]
+	fn finds_continuous_nice_loop() {
+		let mut b = Board::from_pencilmarks("
+			35 7   145 6   19  58 13489 148 2
+			29 356 135 58  129 4  3789  168 3789
+			29 8   146 7   3   12 1469  5   149
+			35 35  7   9   4   6  2     18  18
+			1  26  28  38  5   7  349   46  349
+			4  9   68  12  12  38 36    7   5
+			6  345 35  345 8   9  17    2   17
+			7  1   235 235 6   35 48    9   48
+			8  24  9   124 7   12 5     3   6
+		");
+		let step = chains(&b).unwrap();
+		assert_eq!(step.to_string(), "Continuous Nice Loop: r3c3<>1, r1c7<>4");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(2, 2), vec![4, 6]);
+		assert_eq!(b.candidates(0, 6), vec![1, 3, 8, 9]);
+	}
+}
@@ -0,0 +1,270 @@
+use std::collections::VecDeque;
+
+use crate::board::Board;
+use crate::logic::{sees, Step, Technique};
+
+//Every cell holding a digit, joined by conjugate pairs, where a unit has only those two places for it.
+//Cells joined by a chain of conjugate pairs form a cluster, colored so that the two ends of every pair differ.
+//Exactly one color of each cluster holds the digit.
+pub(crate) struct Graph {
+	cells: Vec<[usize; 2]>, //Cells holding the digit
+	index: Vec<Vec<usize>>, //Position of each cell in cells, usize::MAX for cells without the digit
+	strong: Vec<Vec<usize>>, //Conjugate partners of each cell
+	color: Vec<(usize, bool)>, //Cluster and color of each cell
+	clusters: usize,
+}
+impl Graph {
+
+	//Build the conjugate-pair graph of a digit.
+	fn new(b: &Board, digit: u16) -> Self {
+		let bsize = b.size();
+		let mut index = vec![vec![usize::MAX; bsize]; bsize];
+		let mut cells = vec![];
+		for (i, row) in index.iter_mut().enumerate() {
+			for (j, k) in row.iter_mut().enumerate() {
+				if b.candidate_set(i, j).contains(digit) {
+					*k = cells.len();
+					cells.push([i, j]);
+				}
+			}
+		}
+
+		let mut strong = vec![vec![]; cells.len()];
+		for unit in &b.topology().units {
+			let places: Vec<usize> = unit.iter().map(|each| index[each[0]][each[1]]).filter(|&k| k != usize::MAX).collect();
+			if places.len() == 2 && !strong[places[0]].contains(&places[1]) {
+				strong[places[0]].push(places[1]);
+				strong[places[1]].push(places[0]);
+			}
+		}
+
+		//Color each cluster by walking its conjugate pairs.
+		let mut color = vec![(usize::MAX, false); cells.len()];
+		let mut clusters = 0;
+		for start in 0..cells.len() {
+			if color[start].0 != usize::MAX || strong[start].is_empty() {
+				continue;
+			}
+
+			color[start] = (clusters, false);
+			let mut queue = VecDeque::from([start]);
+			while let Some(k) = queue.pop_front() {
+				for &next in &strong[k] {
+					if color[next].0 == usize::MAX {
+						color[next] = (clusters, !color[k].1);
+						queue.push_back(next);
+					}
+				}
+			}
+			clusters += 1;
+		}
+
+		Self { cells, index, strong, color, clusters }
+	}
+
+	//Cells of one color of a cluster
+	fn colored(&self, cluster: usize, side: bool) -> Vec<[usize; 2]> {
+		(0..self.cells.len()).filter(|&k| self.color[k] == (cluster, side)).map(|k| self.cells[k]).collect()
+	}
+}
+
+//Conjugate-pair graph of every digit, shared by the coloring techniques
+pub(crate) fn graphs(b: &Board) -> Vec<Graph> {
+	(1..=b.size() as u16).map(|d| Graph::new(b, d)).collect()
+}
+
+//Within one cluster, a color with two cells that see each other cannot hold the digit, and a cell outside
+//the cluster that sees both colors cannot either.
+pub(crate) fn simple_coloring(b: &Board, graphs: &[Graph]) -> Option<Step> {
+	for (g, d) in graphs.iter().zip(1..) {
+
+		for cluster in 0..g.clusters {
+			let sides = [g.colored(cluster, false), g.colored(cluster, true)];
+
+			//Color wrap
+			for side in &sides {
+				if side.iter().any(|&a| side.iter().any(|&c| sees(b, a, c))) {
+					return Step::new(Technique::SimpleColoring, side.iter().map(|&each| (each, d)).collect());
+				}
+			}
+
+			//Color trap
+			let step = eliminate(Technique::SimpleColoring, d, g, |each| {
+				g.color[each].0 != cluster && sides.iter().all(|side| side.iter().any(|&c| sees(b, g.cells[each], c)))
+			});
+			if step.is_some() {
+				return step;
+			}
+		}
+	}
+	None
+}
+
+//When a color of one cluster sees a color of another, at most one of them holds the digit, so one of their
+//opposite colors does. Cells that see both opposite colors cannot hold the digit, and a color that sees
+//both colors of another cluster cannot either.
+pub(crate) fn multi_coloring(b: &Board, graphs: &[Graph]) -> Option<Step> {
+	for (g, d) in graphs.iter().zip(1..) {
+		let sides: Vec<[Vec<[usize; 2]>; 2]> = (0..g.clusters).map(|c| [g.colored(c, false), g.colored(c, true)]).collect();
+		let linked = |a: &[[usize; 2]], c: &[[usize; 2]]| a.iter().any(|&x| c.iter().any(|&y| sees(b, x, y)));
+
+		for c1 in 0..g.clusters {
+			for c2 in 0..g.clusters {
+				if c1 == c2 {
+					continue;
+				}
+
+				for s1 in 0..2 {
+					//Wrap
+					if linked(&sides[c1][s1], &sides[c2][0]) && linked(&sides[c1][s1], &sides[c2][1]) {
+						return Step::new(Technique::MultiColoring, sides[c1][s1].iter().map(|&each| (each, d)).collect());
+					}
+
+					//Trap
+					for s2 in 0..2 {
+						if !linked(&sides[c1][s1], &sides[c2][s2]) {
+							continue;
+						}
+
+						let (o1, o2) = (&sides[c1][1-s1], &sides[c2][1-s2]);
+						let step = eliminate(Technique::MultiColoring, d, g, |each| {
+							let cell = g.cells[each];
+							!o1.contains(&cell) && !o2.contains(&cell) && linked(&[cell], o1) && linked(&[cell], o2)
+						});
+						if step.is_some() {
+							return step;
+						}
+					}
+				}
+			}
+		}
+	}
+	None
+}
+
+//A chain of cells holding a digit that alternates between conjugate pairs and cells that see each other,
+//starting and ending with a conjugate pair. If the first cell does not hold the digit, the last one does,
+//so the digit can go in no cell that sees both ends.
+pub(crate) fn x_chain(b: &Board, graphs: &[Graph]) -> Option<Step> {
+	for (g, d) in graphs.iter().zip(1..) {
+
+		for start in 0..g.cells.len() {
+			if g.strong[start].is_empty() {
+				continue;
+			}
+
+			//Cells reached from start, once after a conjugate pair and once after a shared unit.
+			let mut seen = vec![[false; 2]; g.cells.len()];
+			let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+			for &next in &g.strong[start] {
+				seen[next][1] = true;
+				queue.push_back((next, 1));
+			}
+
+			while let Some((k, strong)) = queue.pop_front() {
+				if strong == 1 {
+					//The chain can end here, after a conjugate pair.
+					if k != start && !g.strong[start].contains(&k) {
+						let ends = [g.cells[start], g.cells[k]];
+						let step = eliminate(Technique::XChain, d, g, |each| {
+							!ends.contains(&g.cells[each]) && sees(b, g.cells[each], ends[0]) && sees(b, g.cells[each], ends[1])
+						});
+						if step.is_some() {
+							return step;
+						}
+					}
+
+					//Or carry on to any cell seeing this one.
+					let cell = g.cells[k];
+					for peer in &b.topology().peers[cell[0]][cell[1]].aoe {
+						let next = g.index[peer[0]][peer[1]];
+						if next != usize::MAX && !seen[next][0] {
+							seen[next][0] = true;
+							queue.push_back((next, 0));
+						}
+					}
+				} else {
+					for &next in &g.strong[k] {
+						if !seen[next][1] {
+							seen[next][1] = true;
+							queue.push_back((next, 1));
+						}
+					}
+				}
+			}
+		}
+	}
+	None
+}
+
+//Step removing the digit from every cell of the graph that passes a filter.
+fn eliminate<F: Fn(usize) -> bool>(technique: Technique, digit: u16, g: &Graph, filter: F) -> Option<Step> {
+	let eliminations: Vec<([usize; 2], u16)> = (0..g.cells.len()).filter(|&k| filter(k)).map(|k| (g.cells[k], digit)).collect();
+
+	Step::new(technique, eliminations)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_simple_coloring() {
+		let mut b = Board::from_pencilmarks("
+			4  5   7  29  29 1 6   3  8
+			12 9   12 8   3  6 7   5  4
+			8  3   6  45  45 7 1   9  2
+			7  14  9  126 12 3 45  8  56
+			6  8   5  47  47 9 3   2  1
+			12 124 3  16  8  5 9   46 7
+			5  6   4  79  79 2 8   1  3
+			9  7   12 3   15 8 245 46 56
+			3  12  8  15  6  4 25  7  9
+		");
+		let step = simple_coloring(&b, &graphs(&b)).unwrap();
+		assert_eq!(step.to_string(), "Simple Coloring: r4c2<>1, r6c4<>1");
+		b.apply_step(&step);
+		//Both cells are left with a single, which is placed.
+		assert_eq!((b.digit(3, 1), b.digit(5, 3)), (4, 6));
+	}
+
+	#[test]
+	fn finds_multi_coloring() {
+		let mut b = Board::from_pencilmarks("
+			4589 124589 6    19  1249 7    458  3458 235
+			478  3      1478 5   124  6    9    478  27
+			4579 24579  47   8   3    249  457  6    1
+			789  1789   2    4   5    3    6    17   79
+			4569 14569  14   7   28   28   145  1345 359
+			3457 457    347  6   19   19   457  2    8
+			1    478    9    2   478  458  3    578  6
+			2    478    3478 13  6    1458 1578 9    57
+			3678 678    5    139 1789 189  2    178  4
+		");
+		let step = multi_coloring(&b, &graphs(&b)).unwrap();
+		assert_eq!(step.to_string(), "Multi-Coloring: r1c1<>8, r1c2<>8, r2c8<>8");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(0, 0), vec![4, 5, 9]);
+		assert_eq!(b.candidates(1, 7), vec![4, 7]);
+	}
+
+	#[test]
+	fn finds_x_chain() {
+		let mut b = Board::from_pencilmarks("
+			138  13568 135 1258 4   7 238 9   236
+			1389 13589 2   158  158 6 38  4   7
+			4    7     68  28   9   3 5   1   26
+			7    2     9   13   13  5 4   6   8
+			6    48    48  9    7   2 1   3   5
+			13   135   135 4    6   8 79  27  29
+			5    46    46  7    38  9 23  28  1
+			389  389   7   358  2   1 6   58  4
+			2    138   13  6    35  4 79  578 39
+		");
+		let step = x_chain(&b, &graphs(&b)).unwrap();
+		assert_eq!(step.to_string(), "X-Chain: r2c5<>8, r8c4<>8");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(1, 4), vec![1, 5]);
+		assert_eq!(b.candidates(7, 3), vec![3, 5]);
+	}
+}
@@ -0,0 +1,121 @@
+use crate::board::Board;
+use crate::digits::DigitSet;
+use crate::logic::{find_subset, Step, Technique};
+
+//When a digit of n rows can only go in the same n columns, each of those columns gets the digit in one of
+//the n rows, so it can go nowhere else in the columns. The same holds with rows and columns swapped.
+pub(crate) fn fish(b: &Board, n: usize) -> Option<Step> {
+	let bsize = b.size();
+
+	//For each digit, the columns each row holds it in and the rows each column holds it in,
+	//where bit k-1 stands for the k-th line.
+	let mut places = vec![[vec![DigitSet::EMPTY; bsize], vec![DigitSet::EMPTY; bsize]]; bsize];
+	for i in 0..bsize {
+		for j in 0..bsize {
+			for d in b.candidate_set(i, j) {
+				places[d as usize - 1][0][i].insert(j as u16 + 1);
+				places[d as usize - 1][1][j].insert(i as u16 + 1);
+			}
+		}
+	}
+
+	for d in 1..=bsize as u16 {
+		//A fish of n out of the k lines still missing the digit is the same as
+		//one of k-n lines the other way round, so only the smaller one is searched for.
+		let lines = places[d as usize - 1][0].iter().filter(|p| !p.is_empty()).count();
+		if 2*n > lines {
+			continue;
+		}
+
+		for base_axis in [0, 1] {
+			let cover_axis = 1 - base_axis;
+
+			let mut step = None;
+			find_subset(&places[d as usize - 1][base_axis], n, &mut |base, cover| {
+				let mut eliminations = vec![];
+				for k in cover {
+					for i in (0..bsize).filter(|i| !base.contains(i)) {
+						let mut coord = [0, 0];
+						coord[base_axis] = i;
+						coord[cover_axis] = k as usize - 1;
+						if b.candidate_set(coord[0], coord[1]).contains(d) {
+							eliminations.push((coord, d));
+						}
+					}
+				}
+
+				step = Step::new(Technique::Fish(n), eliminations);
+				step.is_some()
+			});
+
+			if step.is_some() {
+				return step;
+			}
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_x_wing() {
+		let mut b = Board::from_pencilmarks("
+			6   1 458 345  9  38   248 78 247
+			3   9 48  24   7  28   6   1  5
+			458 7 2   456  15 168  3   9  48
+			2   6 1   9    3  5    48  78 47
+			57  3 9   27   8  4    125 6  12
+			457 8 457 1    6  27   25  3  9
+			9   4 678 67   2  167  18  5  3
+			1   5 3   8    4  9    7   2  6
+			78  2 678 3567 15 1367 9   4  18
+		");
+		let step = fish(&b, 2).unwrap();
+		assert_eq!(step.to_string(), "X-Wing: r3c6<>8, r9c3<>8");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(2, 5), vec![1, 6]);
+		assert_eq!(b.candidates(8, 2), vec![6, 7]);
+	}
+
+	#[test]
+	fn finds_swordfish() {
+		let mut b = Board::from_pencilmarks("
+			7   9   12   148 168  168   5  24  3
+			8   4   6    3   5    2     1  7   9
+			123 13  5    9   147  147   6  8   24
+			9   6   378  5   78   3478  24 234 1
+			123 123 1237 6   1479 13479 8  34  5
+			4   5   138  18  2    138   7  9   6
+			136 7   13   128 1689 1689  49 5   24
+			26  8   9    24  46   5     3  1   7
+			5   12  4    7   3    19    29 6   8
+		");
+		let step = fish(&b, 3).unwrap();
+		assert_eq!(step.to_string(), "Swordfish: r5c1<>2");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(4, 0), vec![1, 3]);
+	}
+
+	#[test]
+	fn finds_jellyfish() {
+		let mut b = Board::from_pencilmarks("
+			1   456 3   456  8    9  2      456 7
+			259 456 7   1456 123  35 135689 456 135689
+			8   456 259 1456 123  7  13569  456 13569
+			3   1   59  8    79   4  5679   2   569
+			579 2   6   15   1379 35 3579   8   4
+			579 8   4   2    379  6  3579   1   359
+			6   7   18  3    5    2  4      9   18
+			4   9   15  7    6    8  15     3   2
+			25  3   258 9    4    1  568    7   568
+		");
+		let step = fish(&b, 4).unwrap();
+		assert_eq!(step.to_string(), "Jellyfish: r2c1<>5, r5c1<>5, r3c3<>5, r2c7<>5, r3c7<>5, r5c7<>5, r2c9<>5, r3c9<>5");
+		b.apply_step(&step);
+		assert!(step.eliminations.iter().all(|&(c, d)| !b.candidates(c[0], c[1]).contains(&d)));
+		assert_eq!(b.candidates(4, 0), vec![7, 9]);
+	}
+}
@@ -0,0 +1,108 @@
+use crate::board::Board;
+use crate::logic::{Step, Technique};
+
+//When a digit of a house can only go in one row or column, it can go nowhere else in that line.
+pub(crate) fn pointing(b: &Board) -> Option<Step> {
+	let bsize = b.size();
+	let units = &b.topology().units;
+
+	for house in &units[2*bsize..] {
+		for d in 1..=bsize as u16 {
+			let cells: Vec<[usize; 2]> = house.iter().copied().filter(|each| b.candidate_set(each[0], each[1]).contains(d)).collect();
+			if cells.is_empty() {
+				continue;
+			}
+
+			//Row lines come first in units, then column lines.
+			for (axis, offset) in [(0, 0), (1, bsize)] {
+				let line = cells[0][axis];
+				if cells.iter().all(|each| each[axis] == line) {
+					let step = eliminate(b, Technique::Pointing, d, &units[offset + line], |each| !house.contains(each));
+					if step.is_some() {
+						return step;
+					}
+				}
+			}
+		}
+	}
+	None
+}
+
+//When a digit of a row or column can only go in one house, it can go nowhere else in that house.
+pub(crate) fn claiming(b: &Board) -> Option<Step> {
+	let bsize = b.size();
+	let hsize = b.house_size();
+	let units = &b.topology().units;
+
+	for line in &units[..2*bsize] {
+		for d in 1..=bsize as u16 {
+			let cells: Vec<[usize; 2]> = line.iter().copied().filter(|each| b.candidate_set(each[0], each[1]).contains(d)).collect();
+			if cells.is_empty() {
+				continue;
+			}
+
+			let house = (cells[0][0]/hsize)*hsize + cells[0][1]/hsize;
+			if cells.iter().all(|each| (each[0]/hsize)*hsize + each[1]/hsize == house) {
+				let step = eliminate(b, Technique::Claiming, d, &units[2*bsize + house], |each| !line.contains(each));
+				if step.is_some() {
+					return step;
+				}
+			}
+		}
+	}
+	None
+}
+
+//Step removing a digit from the cells of a unit that pass a filter, if any of them hold it.
+fn eliminate<F: Fn(&[usize; 2]) -> bool>(b: &Board, technique: Technique, digit: u16, unit: &[[usize; 2]], filter: F) -> Option<Step> {
+	let eliminations: Vec<([usize; 2], u16)> = unit.iter()
+		.filter(|each| filter(each) && b.candidate_set(each[0], each[1]).contains(digit))
+		.map(|each| (*each, digit))
+		.collect();
+
+	Step::new(technique, eliminations)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_pointing() {
+		let mut b = Board::from_pencilmarks("
+			8 5 7  3 46 1  49   2   469
+			4 3 1  9 67 2  5    78  678
+			6 9 2  5 8  47 3    1   47
+			3 7 48 1 9  6  2    48  5
+			5 2 48 7 34 34 1489 6   13489
+			9 1 6  2 5  8  47   347 347
+			1 4 9  6 2  5  78   378 378
+			2 6 5  8 37 37 147  9   1347
+			7 8 3  4 1  9  6    5   2
+		");
+		let step = pointing(&b).unwrap();
+		assert_eq!(step.to_string(), "Pointing: r5c9<>3");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(4, 8), vec![1, 4, 8, 9]);
+	}
+
+	#[test]
+	fn finds_claiming() {
+		let mut b = Board::from_pencilmarks("
+			1269 23567 4     2379  67 23679 15679 8     169
+			689  5678  79    479   1  4679  2     45679 3
+			1269 2367  12379 23479 8  5     1679  14679 1469
+			5    9     6     8     47 1347  137   1237  12
+			7    4     8     139   2  139   13569 13569 169
+			12   23    123   6     5  79    4     79    8
+			3    1     27    247   9  2467  8     246   5
+			2468 2678  5     1247  3  12467 69    2469  2469
+			2469 26    29    5     46 8     13    13    7
+		");
+		let step = claiming(&b).unwrap();
+		assert_eq!(step.to_string(), "Claiming: r7c3<>2, r8c1<>2, r8c2<>2");
+		b.apply_step(&step);
+		//Each cell loses its 2 and is left with a single, which is placed.
+		assert_eq!((b.digit(6, 2), b.digit(7, 0), b.digit(7, 1)), (7, 4, 8));
+	}
+}
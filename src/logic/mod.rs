@@ -0,0 +1,250 @@
+use std::cell::OnceCell;
+use std::fmt;
+
+use crate::board::Board;
+use crate::deduction::Deduction;
+use crate::digits::DigitSet;
+
+mod als;
+mod chains;
+mod coloring;
+mod fish;
+mod intersections;
+mod subsets;
+mod uniqueness;
+mod wings;
+
+//Named candidate-analysis technique, tried once hidden and naked singles run dry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Technique {
+	Pointing, //A digit of a house confined to one row or column, ruled out of the rest of that line
+	Claiming, //A digit of a row or column confined to one house, ruled out of the rest of that house
+	NakedSubset(usize), //n cells of a unit holding only n digits between them
+	HiddenSubset(usize), //n digits of a unit with only n cells between them
+	Fish(usize), //A digit of n rows confined to n columns, or of n columns confined to n rows
+	XYWing, //Two bivalue pincers seen by a bivalue pivot, sharing the digit the pivot lacks
+	XYZWing, //Two bivalue pincers seen by a pivot holding all three of their digits
+	WWing, //Two cells holding the same two digits, joined by a unit where one of the digits has two places
+	SimpleColoring, //Contradictions within one cluster of conjugate pairs of a digit
+	MultiColoring, //Contradictions between two clusters of conjugate pairs of a digit
+	XChain, //A chain of one digit alternating between conjugate pairs and shared units
+	XYChain, //A chain of bivalue cells, each passing a digit on to the next, starting and ending on the same digit
+	Aic, //An alternating inference chain, where at least one of its two ends is true
+	DiscontinuousNiceLoop, //An alternating chain that contradicts an assumption about its own start
+	ContinuousNiceLoop, //An alternating chain closing on itself, turning each of its weak links strong
+	UniqueRectangle(usize), //Four corners that would let two digits swap places, of type 1 through 6
+	HiddenRectangle, //A unique rectangle found through the places left for one of its digits
+	BugPlusOne, //Every empty cell bivalue but one, which must hold the digit keeping the solution unique
+	AlsXz, //Two almost locked sets sharing a digit that at most one of them can get
+	AlsXyWing, //Two almost locked sets each sharing such a digit with a third
+	DeathBlossom, //A cell with an almost locked set for each of its digits
+}
+
+impl fmt::Display for Technique {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Technique::Pointing => write!(f, "Pointing"),
+			Technique::Claiming => write!(f, "Claiming"),
+			Technique::NakedSubset(n) => write!(f, "Naked {}", subset_name(*n)),
+			Technique::HiddenSubset(n) => write!(f, "Hidden {}", subset_name(*n)),
+			Technique::Fish(2) => write!(f, "X-Wing"),
+			Technique::Fish(3) => write!(f, "Swordfish"),
+			Technique::Fish(4) => write!(f, "Jellyfish"),
+			Technique::Fish(n) => write!(f, "Fish of size {}", n),
+			Technique::XYWing => write!(f, "XY-Wing"),
+			Technique::XYZWing => write!(f, "XYZ-Wing"),
+			Technique::WWing => write!(f, "W-Wing"),
+			Technique::SimpleColoring => write!(f, "Simple Coloring"),
+			Technique::MultiColoring => write!(f, "Multi-Coloring"),
+			Technique::XChain => write!(f, "X-Chain"),
+			Technique::XYChain => write!(f, "XY-Chain"),
+			Technique::Aic => write!(f, "AIC"),
+			Technique::DiscontinuousNiceLoop => write!(f, "Discontinuous Nice Loop"),
+			Technique::ContinuousNiceLoop => write!(f, "Continuous Nice Loop"),
+			Technique::UniqueRectangle(n) => write!(f, "Unique Rectangle Type {}", n),
+			Technique::HiddenRectangle => write!(f, "Hidden Unique Rectangle"),
+			Technique::BugPlusOne => write!(f, "BUG+1"),
+			Technique::AlsXz => write!(f, "ALS-XZ"),
+			Technique::AlsXyWing => write!(f, "ALS-XY-Wing"),
+			Technique::DeathBlossom => write!(f, "Death Blossom"),
+		}
+	}
+}
+
+impl Technique {
+
+	//How hard the technique is to spot by hand, higher being harder. Singles would score 0.
+	pub fn difficulty(&self) -> u32 {
+		match self {
+			Technique::Pointing | Technique::Claiming => 50,
+			Technique::NakedSubset(n) => 20 + 20*(*n as u32),
+			Technique::HiddenSubset(n) => 30 + 25*(*n as u32),
+			Technique::UniqueRectangle(_) | Technique::HiddenRectangle | Technique::BugPlusOne => 100,
+			Technique::Fish(n) => 120 + 10*(*n as u32),
+			Technique::WWing | Technique::SimpleColoring => 150,
+			Technique::XYWing => 160,
+			Technique::XYZWing => 180,
+			Technique::MultiColoring => 200,
+			Technique::XChain | Technique::XYChain => 260,
+			Technique::Aic | Technique::DiscontinuousNiceLoop | Technique::ContinuousNiceLoop => 280,
+			Technique::AlsXz => 300,
+			Technique::AlsXyWing => 320,
+			Technique::DeathBlossom => 360,
+		}
+	}
+}
+
+//How hard a puzzle is to solve without guessing, going by the hardest technique it needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rating {
+	Singles, //Hidden and naked singles are enough
+	Technique(Technique), //The hardest named technique needed
+	Guessing, //Candidate analysis runs out of steps before the board is solved
+}
+
+impl fmt::Display for Rating {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Rating::Singles => write!(f, "Singles"),
+			Rating::Technique(technique) => write!(f, "{} ({})", technique, technique.difficulty()),
+			Rating::Guessing => write!(f, "Guessing"),
+		}
+	}
+}
+
+//Name of a subset of n cells or digits
+fn subset_name(n: usize) -> String {
+	match n {
+		1 => String::from("Single"),
+		2 => String::from("Pair"),
+		3 => String::from("Triple"),
+		4 => String::from("Quad"),
+		n => format!("Subset of {}", n),
+	}
+}
+
+//Candidates a technique rules out, each as the cell and the digit removed from it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+	pub technique: Technique,
+	pub eliminations: Vec<([usize; 2], u16)>,
+}
+impl Step {
+
+	//Step of a technique, if it eliminates anything
+	pub(crate) fn new(technique: Technique, eliminations: Vec<([usize; 2], u16)>) -> Option<Step> {
+		if eliminations.is_empty() {
+			None
+		} else {
+			Some(Step { technique, eliminations })
+		}
+	}
+}
+
+//Technique followed by each elimination, such as "X-Wing: r1c5<>3, r7c5<>3", counting from 1.
+impl fmt::Display for Step {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}:", self.technique)?;
+		for (k, (coord, digit)) in self.eliminations.iter().enumerate() {
+			let sep = if k == 0 { " " } else { ", " };
+			write!(f, "{}r{}c{}<>{}", sep, coord[0]+1, coord[1]+1, digit)?;
+		}
+		Ok(())
+	}
+}
+
+//Finder of a technique, or of a group of techniques that tries them in order of difficulty
+type Finder<'a> = Box<dyn Fn() -> Option<Step> + 'a>;
+
+//Find the next step of a board, trying techniques in order of difficulty and none past deduction, so that a
+//rating never goes by a harder technique than the board needed. Uniqueness techniques are only tried when
+//unique is set, as they rely on the puzzle having a single solution.
+pub(crate) fn find_step(b: &Board, deduction: Deduction, unique: bool) -> Option<Step> {
+	let graphs = OnceCell::new();
+	let mut finders: Vec<(Technique, Finder)> = vec![];
+
+	if deduction >= Deduction::Intersections {
+		finders.push((Technique::Pointing, Box::new(|| intersections::pointing(b))));
+		finders.push((Technique::Claiming, Box::new(|| intersections::claiming(b))));
+	}
+	if deduction >= Deduction::Subsets {
+		for n in 2..=4 {
+			finders.push((Technique::NakedSubset(n), Box::new(move || subsets::naked(b, n))));
+			finders.push((Technique::HiddenSubset(n), Box::new(move || subsets::hidden(b, n))));
+		}
+	}
+	if deduction >= Deduction::Full {
+		//Fish of more than half the lines are the same as smaller fish the other way round.
+		for n in 2..=b.size()/2 {
+			finders.push((Technique::Fish(n), Box::new(move || fish::fish(b, n))));
+		}
+		finders.push((Technique::XYWing, Box::new(|| wings::xy_wing(b))));
+		finders.push((Technique::XYZWing, Box::new(|| wings::xyz_wing(b))));
+		finders.push((Technique::WWing, Box::new(|| wings::w_wing(b))));
+		if unique {
+			finders.push((Technique::UniqueRectangle(1), Box::new(|| uniqueness::uniqueness(b))));
+		}
+
+		//The coloring techniques share the graph of each digit, built once the first of them is tried.
+		let graphs = &graphs;
+		finders.push((Technique::SimpleColoring, Box::new(move || coloring::simple_coloring(b, graphs.get_or_init(|| coloring::graphs(b))))));
+		finders.push((Technique::MultiColoring, Box::new(move || coloring::multi_coloring(b, graphs.get_or_init(|| coloring::graphs(b))))));
+		finders.push((Technique::XChain, Box::new(move || coloring::x_chain(b, graphs.get_or_init(|| coloring::graphs(b))))));
+		finders.push((Technique::XYChain, Box::new(|| chains::chains(b))));
+		finders.push((Technique::AlsXz, Box::new(|| als::als(b))));
+	}
+
+	//Stable, so techniques of the same difficulty keep the order above.
+	finders.sort_by_key(|(technique, _)| technique.difficulty());
+	finders.iter().find_map(|(_, finder)| finder())
+}
+
+//Empty cells whose candidates pass a filter, in row-major order
+pub(crate) fn cells_with<F: Fn(DigitSet) -> bool>(b: &Board, filter: F) -> Vec<[usize; 2]> {
+	let mut cells = vec![];
+	for i in 0..b.size() {
+		for j in 0..b.size() {
+			let cand = b.candidate_set(i, j);
+			if !cand.is_empty() && filter(cand) {
+				cells.push([i, j]);
+			}
+		}
+	}
+	cells
+}
+
+//Whether two different cells share a row, column or house
+pub(crate) fn sees(b: &Board, a: [usize; 2], c: [usize; 2]) -> bool {
+	let hsize = b.house_size();
+	a != c && (a[0] == c[0] || a[1] == c[1] || (a[0]/hsize == c[0]/hsize && a[1]/hsize == c[1]/hsize))
+}
+
+//Find n of the sets whose union holds exactly n members, calling found with their indices and union
+//until it returns true. Sets that are empty or larger than n are skipped.
+pub(crate) fn find_subset<F: FnMut(&[usize], DigitSet) -> bool>(sets: &[DigitSet], n: usize, found: &mut F) -> bool {
+	let usable: Vec<usize> = (0..sets.len()).filter(|&k| !sets[k].is_empty() && sets[k].len() <= n).collect();
+	let mut chosen: Vec<usize> = vec![];
+	extend_subset(sets, &usable, n, 0, DigitSet::EMPTY, &mut chosen, found)
+}
+
+//Grow a partial subset with usable sets from index start onwards, backing out once the union grows past n.
+fn extend_subset<F: FnMut(&[usize], DigitSet) -> bool>(sets: &[DigitSet], usable: &[usize], n: usize, start: usize,
+	union: DigitSet, chosen: &mut Vec<usize>, found: &mut F) -> bool {
+	if chosen.len() == n {
+		return union.len() == n && found(chosen, union);
+	}
+
+	for k in start..usable.len() {
+		let next = union | sets[usable[k]];
+		if next.len() > n {
+			continue;
+		}
+
+		chosen.push(usable[k]);
+		if extend_subset(sets, usable, n, k+1, next, chosen, found) {
+			return true;
+		}
+		chosen.pop();
+	}
+	false
+}
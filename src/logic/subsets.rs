@@ -0,0 +1,199 @@
+use crate::board::Board;
+use crate::digits::DigitSet;
+use crate::logic::{find_subset, Step, Technique};
+
+//When n empty cells of a unit hold only n digits between them, those digits can go nowhere else in the unit.
+pub(crate) fn naked(b: &Board, n: usize) -> Option<Step> {
+	for unit in &b.topology().units {
+		let cand: Vec<DigitSet> = unit.iter().map(|each| b.candidate_set(each[0], each[1])).collect();
+		let mut step = None;
+
+		find_subset(&cand, n, &mut |cells, digits| {
+			let mut eliminations = vec![];
+			for (k, each) in unit.iter().enumerate() {
+				if !cells.contains(&k) {
+					for d in cand[k] & digits {
+						eliminations.push((*each, d));
+					}
+				}
+			}
+
+			step = Step::new(Technique::NakedSubset(n), eliminations);
+			step.is_some()
+		});
+
+		if step.is_some() {
+			return step;
+		}
+	}
+	None
+}
+
+//When n digits of a unit can only go in the same n cells, those cells can hold no other digits.
+pub(crate) fn hidden(b: &Board, n: usize) -> Option<Step> {
+	let bsize = b.size();
+
+	for unit in &b.topology().units {
+		let cand: Vec<DigitSet> = unit.iter().map(|each| b.candidate_set(each[0], each[1])).collect();
+
+		//Cells each digit can go in, where bit k-1 stands for the k-th cell of the unit
+		let mut places = vec![DigitSet::EMPTY; bsize];
+		for (k, &c) in cand.iter().enumerate() {
+			for d in c {
+				places[d as usize - 1].insert(k as u16 + 1);
+			}
+		}
+
+		let mut step = None;
+		find_subset(&places, n, &mut |digits, cells| {
+			let digits: DigitSet = digits.iter().map(|&d| d as u16 + 1).collect();
+			let mut eliminations = vec![];
+			for k in cells {
+				for d in cand[k as usize - 1] - digits {
+					eliminations.push((unit[k as usize - 1], d));
+				}
+			}
+
+			step = Step::new(Technique::HiddenSubset(n), eliminations);
+			step.is_some()
+		});
+
+		if step.is_some() {
+			return step;
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_naked_pair() {
+		let mut b = Board::from_pencilmarks("
+			4  7 1 6    2   58   9   3  58
+			68 5 2 18   3   9    7   16 4
+			68 9 3 4    157 1578 158 2  1568
+			2  6 9 1378 17  1378 4   5  18
+			1  4 5 28   9   268  68  7  3
+			3  8 7 15   156 4    16  9  2
+			59 2 8 379  4   37   135 16 156
+			59 1 6 239  8   23   35  4  7
+			7  3 4 15   156 156  2   8  9
+		");
+		let step = naked(&b, 2).unwrap();
+		assert_eq!(step.to_string(), "Naked Pair: r2c4<>1, r4c4<>1");
+
+		//r2c4 is left with its 8, and the naked singles that follow finish the board.
+		b.apply_step(&step);
+		assert_eq!(b.digit(1, 3), 8);
+		assert!(b.is_solved());
+		assert_eq!(b.validate(), Ok(()));
+	}
+
+	#[test]
+	fn finds_naked_triple() {
+		let mut b = Board::from_pencilmarks("
+			56  4  7   156 168  168 2  9  3
+			9   3  25  45  7    24  8  6  1
+			26  1  8   69  2369 369 7  5  4
+			245 25 245 7   16   16  3  8  9
+			7   6  1   8   39   39  45 24 25
+			8   9  3   2   4    5   1  7  6
+			1   8  9   3   5    24  6  24 7
+			24  7  6   149 1289 189 45 3  25
+			3   25 245 46  26   7   9  1  8
+		");
+		let step = naked(&b, 3).unwrap();
+		assert_eq!(step.to_string(), "Naked Triple: r8c4<>4, r8c5<>2");
+
+		b.apply_step(&step);
+		assert_eq!(b.candidates(7, 3), vec![1, 9]);
+		assert_eq!(b.candidates(7, 4), vec![1, 8, 9]);
+	}
+
+	#[test]
+	fn finds_naked_quad() {
+		let mut b = Board::from_pencilmarks("
+			46   2  9    8   3   1    46   5    7
+			45   16 7    459 2   49   3    8    16
+			8    3  145  456 46  7    2    14   9
+			7    5  12   469 469 3    1689 19   1268
+			139  8  6    7   5   2    149  1349 13
+			239  4  23   1   8   69   5    7    236
+			1456 16 145  2   7   4689 189  139  138
+			1234 7  1234 349 149 489  189  6    5
+			136  9  8    36  16  5    7    2    4
+		");
+		let step = naked(&b, 4).unwrap();
+		assert_eq!(step.to_string(), "Naked Quad: r4c7<>1, r4c7<>9, r4c9<>1, r6c9<>3");
+
+		b.apply_step(&step);
+		assert_eq!(b.candidates(3, 6), vec![6, 8]);
+		assert_eq!(b.candidates(3, 8), vec![2, 6, 8]);
+		assert_eq!(b.candidates(5, 8), vec![2, 6]);
+	}
+	#[test]
+	fn finds_hidden_pair() {
+		let mut b = Board::from_pencilmarks("
+			1269  23567 4     2379  67 23679 15679 8      169
+			689   5678  79    479   1  4679  2     45679  3
+			1269  2367  12379 23479 8  5     1679  14679  1469
+			5     9     6     8     47 1347  137   1237   12
+			7     4     8     139   2  139   13569 13569  169
+			12    23    123   6     5  79    4     79     8
+			3     1     27    247   9  2467  8     246    5
+			24689 2678  5     1247  3  12467 69    2469   2469
+			2469  26    29    5     46 8     1369  123469 7
+		");
+		let step = hidden(&b, 2).unwrap();
+		assert_eq!(step.to_string(), "Hidden Pair: r9c7<>6, r9c7<>9, r9c8<>2, r9c8<>4, r9c8<>6, r9c8<>9");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(8, 6), vec![1, 3]);
+		assert_eq!(b.candidates(8, 7), vec![1, 3]);
+	}
+
+	#[test]
+	fn finds_hidden_triple() {
+		let mut b = Board::from_pencilmarks("
+			5    34  37   47 6   2  8  1  9
+			14   2   6    14 9   8  3  5  7
+			179  18  89   5  37  13 2  4  6
+			137  13  1237 6  237 5  4  9  8
+			3479 348 89   17 237 13 57 6  25
+			6    57  257  9  8   4  1  27 3
+			2    57  57   8  4   6  9  3  1
+			13   9   13   2  5   7  6  8  4
+			8    6   4    3  1   9  57 27 25
+		");
+		let step = hidden(&b, 3).unwrap();
+		assert_eq!(step.to_string(), "Hidden Triple: r5c1<>3, r5c1<>7, r5c2<>3");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(4, 0), vec![4, 9]);
+		assert_eq!(b.candidates(4, 1), vec![4, 8]);
+	}
+
+	#[test]
+	fn finds_hidden_quad() {
+		let mut b = Board::from_pencilmarks("
+			4  7 1 6     2   58    9    3  58
+			68 5 2 18    3   9     7    16 4
+			68 9 3 4     157 1578  1568 2  1568
+			2  6 9 1378  17  1378  4    5  18
+			1  4 5 28    9   268   68   7  3
+			3  8 7 15    156 4     16   9  2
+			59 2 8 13579 4   13567 1356 16 156
+			59 1 6 2359  8   235   35   4  7
+			7  3 4 15    156 156   2    8  9
+		");
+		let step = hidden(&b, 4).unwrap();
+		assert_eq!(step.to_string(), "Hidden Quad: r4c4<>1, r4c4<>8, r5c4<>8, r7c4<>1, r7c4<>5, r8c4<>5");
+		b.apply_step(&step);
+		//r5c4 is left with its 2, which is placed.
+		assert_eq!(b.digit(4, 3), 2);
+		assert_eq!(b.candidates(3, 3), vec![3, 7]);
+		assert_eq!(b.candidates(6, 3), vec![3, 7, 9]);
+		assert_eq!(b.candidates(7, 3), vec![3, 9]);
+	}
+}
@@ -0,0 +1,450 @@
+use crate::board::Board;
+use crate::digits::DigitSet;
+use crate::logic::{find_subset, sees, Step, Technique};
+
+//Four empty cells on the corners of a rectangle within two houses, all of which could hold the digits a and b.
+//Were the four cells left with nothing else, a and b could be swapped between them and the puzzle would have a
+//second solution, so on a puzzle with a single solution at least one corner holds some other digit.
+struct Rectangle {
+	corners: [[usize; 2]; 4], //Top left, top right, bottom left, bottom right, so the opposite of k is 3-k
+	pair: DigitSet, //a and b
+}
+impl Rectangle {
+
+	//Corners holding no more than a and b, or holding more when floor is not set
+	fn corners(&self, b: &Board, floor: bool) -> Vec<usize> {
+		(0..4).filter(|&k| {
+			let c = self.corners[k];
+			(b.candidate_set(c[0], c[1]) == self.pair) == floor
+		}).collect()
+	}
+
+	//Units holding both of two corners
+	fn shared_units<'a>(&self, b: &'a Board, k1: usize, k2: usize) -> Vec<&'a Vec<[usize; 2]>> {
+		let (c1, c2) = (self.corners[k1], self.corners[k2]);
+		let bsize = b.size();
+		let hsize = b.house_size();
+		let units = &b.topology().units;
+
+		let mut shared = vec![];
+		if c1[0] == c2[0] {
+			shared.push(&units[c1[0]]);
+		}
+		if c1[1] == c2[1] {
+			shared.push(&units[bsize + c1[1]]);
+		}
+		if c1[0]/hsize == c2[0]/hsize && c1[1]/hsize == c2[1]/hsize {
+			shared.push(&units[2*bsize + (c1[0]/hsize)*hsize + c1[1]/hsize]);
+		}
+		shared
+	}
+}
+
+//Every rectangle of the board and pair of digits its corners have in common
+fn rectangles(b: &Board) -> Vec<Rectangle> {
+	let bsize = b.size();
+	let hsize = b.house_size();
+	let mut found = vec![];
+
+	for r1 in 0..bsize {
+		for r2 in r1+1..bsize {
+			for c1 in 0..bsize {
+				for c2 in c1+1..bsize {
+					//Four houses would stop a and b from being swapped.
+					if r1/hsize != r2/hsize && c1/hsize != c2/hsize {
+						continue;
+					}
+
+					let corners = [[r1, c1], [r1, c2], [r2, c1], [r2, c2]];
+					let common = corners.iter().fold(DigitSet::full(bsize), |acc, c| acc & b.candidate_set(c[0], c[1]));
+					for a in common {
+						for d in common.iter().filter(|&d| d > a) {
+							found.push(Rectangle { corners, pair: DigitSet::single(a) | DigitSet::single(d) });
+						}
+					}
+				}
+			}
+		}
+	}
+	found
+}
+
+//Try every type of unique rectangle, then BUG+1. Only valid on puzzles with a single solution.
+pub(crate) fn uniqueness(b: &Board) -> Option<Step> {
+	let rects = rectangles(b);
+	let kinds: [fn(&Board, &Rectangle) -> Option<Step>; 6] = [type_1, type_2_5, type_3, type_4, type_6, hidden];
+
+	for kind in kinds {
+		for rect in &rects {
+			let step = kind(b, rect);
+			if step.is_some() {
+				return step;
+			}
+		}
+	}
+	bug(b)
+}
+
+//Three corners hold only a and b, so the fourth cannot hold either.
+fn type_1(b: &Board, rect: &Rectangle) -> Option<Step> {
+	let roof = rect.corners(b, false);
+	if roof.len() != 1 {
+		return None;
+	}
+
+	let c = rect.corners[roof[0]];
+	let eliminations = (b.candidate_set(c[0], c[1]) & rect.pair).iter().map(|d| (c, d)).collect();
+	Step::new(Technique::UniqueRectangle(1), eliminations)
+}
+
+//Every corner holding more than a and b holds the same one extra digit, so one of them gets it and it can
+//go in no cell that sees all of them. Type 2 has two such corners side by side, type 5 any other layout.
+fn type_2_5(b: &Board, rect: &Rectangle) -> Option<Step> {
+	let roof = rect.corners(b, false);
+	if roof.len() < 2 {
+		return None;
+	}
+
+	let first = rect.corners[roof[0]];
+	let extra = b.candidate_set(first[0], first[1]) - rect.pair;
+	if extra.len() != 1 || roof.iter().any(|&k| b.candidate_set(rect.corners[k][0], rect.corners[k][1]) - rect.pair != extra) {
+		return None;
+	}
+
+	let x = extra.first().unwrap();
+	let cells: Vec<[usize; 2]> = roof.iter().map(|&k| rect.corners[k]).collect();
+	let mut eliminations = vec![];
+	for &each in &b.topology().peers[first[0]][first[1]].aoe {
+		if !cells.contains(&each) && cells.iter().all(|&c| sees(b, each, c)) && b.candidate_set(each[0], each[1]).contains(x) {
+			eliminations.push((each, x));
+		}
+	}
+
+	let kind = if roof.len() == 2 && roof[0] + roof[1] != 3 { 2 } else { 5 };
+	Step::new(Technique::UniqueRectangle(kind), eliminations)
+}
+
+//Two corners side by side hold more than a and b, so one of them holds one of their extra digits. Those digits
+//act as one more cell of any unit holding both corners, and can form a naked subset with other cells of it.
+fn type_3(b: &Board, rect: &Rectangle) -> Option<Step> {
+	let roof = rect.corners(b, false);
+	if roof.len() != 2 || roof[0] + roof[1] == 3 {
+		return None;
+	}
+
+	let (c1, c2) = (rect.corners[roof[0]], rect.corners[roof[1]]);
+	let extra = (b.candidate_set(c1[0], c1[1]) | b.candidate_set(c2[0], c2[1])) - rect.pair;
+
+	for unit in rect.shared_units(b, roof[0], roof[1]) {
+		//The first corner stands in for both, holding just the extra digits.
+		let pseudo = unit.iter().position(|&each| each == c1).unwrap();
+		let cand: Vec<DigitSet> = unit.iter().map(|&each| {
+			if each == c1 {
+				extra
+			} else if each == c2 {
+				DigitSet::EMPTY
+			} else {
+				b.candidate_set(each[0], each[1])
+			}
+		}).collect();
+
+		for n in 2..=4 {
+			let mut step = None;
+			find_subset(&cand, n, &mut |cells, digits| {
+				if !cells.contains(&pseudo) {
+					return false;
+				}
+
+				let mut eliminations = vec![];
+				for (k, &each) in unit.iter().enumerate() {
+					if !cells.contains(&k) && each != c2 {
+						for d in cand[k] & digits {
+							eliminations.push((each, d));
+						}
+					}
+				}
+
+				step = Step::new(Technique::UniqueRectangle(3), eliminations);
+				step.is_some()
+			});
+
+			if step.is_some() {
+				return step;
+			}
+		}
+	}
+	None
+}
+
+//Two corners side by side hold more than a and b, and a unit holding both has nowhere else for a. One of them
+//gets a, so the other cannot get b, and neither can.
+fn type_4(b: &Board, rect: &Rectangle) -> Option<Step> {
+	let roof = rect.corners(b, false);
+	if roof.len() != 2 || roof[0] + roof[1] == 3 {
+		return None;
+	}
+
+	let cells = [rect.corners[roof[0]], rect.corners[roof[1]]];
+	for unit in rect.shared_units(b, roof[0], roof[1]) {
+		for a in rect.pair {
+			if unit.iter().any(|each| !cells.contains(each) && b.candidate_set(each[0], each[1]).contains(a)) {
+				continue;
+			}
+
+			let other = (rect.pair - DigitSet::single(a)).first().unwrap();
+			return Step::new(Technique::UniqueRectangle(4), cells.iter().map(|&c| (c, other)).collect());
+		}
+	}
+	None
+}
+
+//Two opposite corners hold only a and b, and a has no other place in either row or either column. Were a in
+//one of the other two corners, it would have to be in both, leaving b for the first two.
+fn type_6(b: &Board, rect: &Rectangle) -> Option<Step> {
+	let floor = rect.corners(b, true);
+	if floor.len() != 2 || floor[0] + floor[1] != 3 {
+		return None;
+	}
+
+	let [r1, c1] = rect.corners[0];
+	let [r2, c2] = rect.corners[3];
+	let bsize = b.size();
+	let units = &b.topology().units;
+	let lines = [&units[r1], &units[r2], &units[bsize + c1], &units[bsize + c2]];
+
+	for a in rect.pair {
+		let confined = lines.iter().all(|line| {
+			line.iter().all(|each| rect.corners.contains(each) || !b.candidate_set(each[0], each[1]).contains(a))
+		});
+		if !confined {
+			continue;
+		}
+
+		let roof = rect.corners(b, false);
+		return Step::new(Technique::UniqueRectangle(6), roof.iter().map(|&k| (rect.corners[k], a)).collect());
+	}
+	None
+}
+
+//A corner holds only a and b, and a has no other place in the row or the column of the opposite corner. Were
+//that corner b, a would fill both corners beside it, leaving b for the first one.
+fn hidden(b: &Board, rect: &Rectangle) -> Option<Step> {
+	for k in rect.corners(b, true) {
+		let opposite = rect.corners[3 - k];
+		let row = &b.topology().peers[opposite[0]][opposite[1]].row;
+		let col = &b.topology().peers[opposite[0]][opposite[1]].col;
+
+		for a in rect.pair {
+			let confined = row.iter().chain(col.iter()).all(|each| {
+				rect.corners.contains(each) || !b.candidate_set(each[0], each[1]).contains(a)
+			});
+			if !confined {
+				continue;
+			}
+
+			let other = (rect.pair - DigitSet::single(a)).first().unwrap();
+			if b.candidate_set(opposite[0], opposite[1]).contains(other) {
+				return Step::new(Technique::HiddenRectangle, vec![(opposite, other)]);
+			}
+		}
+	}
+	None
+}
+
+//Every empty cell but one holds two candidates and that one holds three. Were the extra digit ruled out of it,
+//every digit would have two places in each unit, which never leaves a single solution. So the cell gets the one
+//digit that would leave it, and can hold neither of the others.
+fn bug(b: &Board) -> Option<Step> {
+	let bsize = b.size();
+	let mut odd = None;
+	for i in 0..bsize {
+		for j in 0..bsize {
+			match b.candidate_set(i, j).len() {
+				0 | 2 => {}
+				3 if odd.is_none() => odd = Some([i, j]),
+				_ => return None,
+			}
+		}
+	}
+	let cell = odd?;
+	let cand = b.candidate_set(cell[0], cell[1]);
+
+	for x in cand {
+		let bug = b.topology().units.iter().all(|unit| {
+			(1..=bsize as u16).all(|d| {
+				let places = unit.iter().filter(|&&each| {
+					let c = b.candidate_set(each[0], each[1]);
+					c.contains(d) && !(each == cell && d == x)
+				}).count();
+				places == 0 || places == 2
+			})
+		});
+
+		if bug {
+			return Step::new(Technique::BugPlusOne, (cand - DigitSet::single(x)).iter().map(|d| (cell, d)).collect());
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_type_1() {
+		let mut b = Board::from_pencilmarks("
+			5    34  37   47 6   2  8  1  9
+			14   2   6    14 9   8  3  5  7
+			179  18  89   5  37  13 2  4  6
+			137  13  1237 6  237 5  4  9  8
+			3479 348 89   17 237 13 57 6  25
+			6    57  257  9  8   4  1  27 3
+			2    57  57   8  4   6  9  3  1
+			13   9   13   2  5   7  6  8  4
+			8    6   4    3  1   9  57 27 25
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "Unique Rectangle Type 1: r6c3<>5, r6c3<>7");
+		b.apply_step(&step);
+		//r6c3 is left with its 2, which is placed.
+		assert_eq!(b.digit(5, 2), 2);
+	}
+
+	#[test]
+	fn finds_type_2() {
+		let mut b = Board::from_pencilmarks("
+			9  3  7  1    4  8  25  6  25
+			8  4  1  25   25 6  7   9  3
+			6  2  5  7    3  9  18  18 4
+			1  68 26 3    9  5  4   7  28
+			5  9  23 4    8  7  123 12 6
+			37 78 4  26   26 1  39  5  89
+			37 67 8  569  1  34 259 24 259
+			4  1  69 5689 56 2  589 3  7
+			2  5  39 89   7  34 6   48 1
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "Unique Rectangle Type 2: r8c7<>9, r7c4<>9");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(7, 6), vec![5, 8]);
+		assert_eq!(b.candidates(6, 3), vec![5, 6]);
+	}
+
+	#[test]
+	fn finds_type_3() {
+		let mut b = Board::from_pencilmarks("
+			56  4  7   156 168  168 2  9  3
+			9   3  25  45  7    24  8  6  1
+			26  1  8   69  2369 369 7  5  4
+			245 25 245 7   16   16  3  8  9
+			7   6  1   8   39   39  45 24 25
+			8   9  3   2   4    5   1  7  6
+			1   8  9   3   5    24  6  24 7
+			24  7  6   19  189  189 45 3  25
+			3   25 245 46  26   7   9  1  8
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "Unique Rectangle Type 3: r3c4<>6");
+		b.apply_step(&step);
+		assert_eq!(b.digit(2, 3), 9);
+	}
+
+	#[test]
+	fn finds_type_4() {
+		let mut b = Board::from_pencilmarks("
+			6   1 458 345  9  38   248 78 2478
+			3   9 48  24   7  28   6   1  5
+			458 7 2   456  15 168  3   9  48
+			2   6 1   9    3  5    48  78 478
+			57  3 9   27   8  4    125 6  12
+			457 8 457 1    6  27   25  3  9
+			9   4 678 67   2  167  18  5  3
+			1   5 3   8    4  9    7   2  6
+			78  2 678 3567 15 1367 9   4  18
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "Unique Rectangle Type 4: r1c9<>8, r4c9<>8");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(0, 8), vec![2, 4, 7]);
+		assert_eq!(b.candidates(3, 8), vec![4, 7]);
+	}
+
+	#[test]
+	fn finds_type_5() {
+		let mut b = Board::from_pencilmarks("
+			26 169 45  245 19 3 8 7 245
+			23 79  245 245 79 8 6 1 245
+			28 17  245 245 17 6 3 9 45
+			26 16  12  9   3  5 4 8 7
+			5  3   9   7   8  4 2 6 1
+			4  8   7   1   6  2 5 3 9
+			9  4   8   6   2  7 1 5 3
+			1  5   3   8   4  9 7 2 6
+			7  2   6   3   5  1 9 4 8
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "Unique Rectangle Type 5: r1c1<>2");
+		b.apply_step(&step);
+		assert_eq!(b.digit(0, 0), 6);
+	}
+
+	#[test]
+	fn finds_type_6() {
+		let mut b = Board::from_pencilmarks("
+			245 2457 6    1  9    47   47    3    8
+			1   478  9    36 3468 3478 467   2    5
+			34  478  3478 2  468  5    4679  1469 147
+			49  1478 478  36 5    138  34679 1469 2
+			29  1278 78   4  368  138  5     169  137
+			6   3    5    9  7    2    8     14   14
+			7   6    2    8  1    9    34    5    34
+			345 45   34   7  2    6    1     8    9
+			8   9    1    5  34   34   2     7    6
+		");
+		let step = rectangles(&b).iter().find_map(|rect| type_6(&b, rect)).unwrap();
+		assert_eq!(step.to_string(), "Unique Rectangle Type 6: r3c3<>3, r8c1<>3");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(2, 2), vec![4, 7, 8]);
+		assert_eq!(b.candidates(7, 0), vec![4, 5]);
+	}
+
+	#[test]
+	fn finds_hidden_rectangle() {
+		let mut b = Board::from_pencilmarks("
+			46   2  9    8   3   1    46  5    7
+			45   16 7    459 2   49   3   8    16
+			8    3  145  456 46  7    2   14   9
+			7    5  12   469 469 3    68  19   268
+			19   8  6    7   5   2    149 1349 13
+			239  4  23   1   8   69   5   7    26
+			1456 16 145  2   7   4689 189 139  138
+			1234 7  1234 349 149 489  189 6    5
+			136  9  8    36  16  5    7   2    4
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "Hidden Unique Rectangle: r7c8<>1");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(6, 7), vec![3, 9]);
+	}
+
+	#[test]
+	fn finds_bug_plus_one() {
+		let mut b = Board::from_pencilmarks("
+			5   34 37 47 6  2  8 1 9
+			14  2  6  14 9  8  3 5 7
+			179 18 89 5  37 13 2 4 6
+			17  13 37 6  2  5  4 9 8
+			49  48 89 17 37 13 5 6 2
+			6   5  2  9  8  4  1 7 3
+			2   7  5  8  4  6  9 3 1
+			3   9  1  2  5  7  6 8 4
+			8   6  4  3  1  9  7 2 5
+		");
+		let step = uniqueness(&b).unwrap();
+		assert_eq!(step.to_string(), "BUG+1: r3c1<>7, r3c1<>9");
+		b.apply_step(&step);
+		//The only way out of the deadly pattern is the 1.
+		assert_eq!(b.digit(2, 0), 1);
+	}
+}
@@ -0,0 +1,173 @@
+use crate::board::Board;
+use crate::digits::DigitSet;
+use crate::logic::{cells_with, sees, Step, Technique};
+
+//A pivot holding x and y sees a pincer holding x and z and another holding y and z. Whichever digit the pivot
+//gets, one of the pincers is z, so z can go in no cell that sees both pincers.
+pub(crate) fn xy_wing(b: &Board) -> Option<Step> {
+	for pivot in cells_with(b, |cand| cand.len() == 2) {
+		let p = b.candidate_set(pivot[0], pivot[1]);
+		let pincers = pincers(b, pivot, |cand| cand.len() == 2 && (cand & p).len() == 1);
+
+		for (k, &a) in pincers.iter().enumerate() {
+			for &c in &pincers[k+1..] {
+				let (ca, cc) = (b.candidate_set(a[0], a[1]), b.candidate_set(c[0], c[1]));
+				let z = ca - p;
+				if ca & p == cc & p || cc - p != z {
+					continue;
+				}
+
+				let step = eliminate(b, Technique::XYWing, z, &[a, c]);
+				if step.is_some() {
+					return step;
+				}
+			}
+		}
+	}
+	None
+}
+
+//A pivot holding x, y and z sees a pincer holding x and z and another holding y and z. Whichever digit the
+//pivot gets, one of the three is z, so z can go in no cell that sees all three.
+pub(crate) fn xyz_wing(b: &Board) -> Option<Step> {
+	for pivot in cells_with(b, |cand| cand.len() == 3) {
+		let p = b.candidate_set(pivot[0], pivot[1]);
+		let pincers = pincers(b, pivot, |cand| cand.len() == 2 && (cand - p).is_empty());
+
+		for (k, &a) in pincers.iter().enumerate() {
+			for &c in &pincers[k+1..] {
+				let (ca, cc) = (b.candidate_set(a[0], a[1]), b.candidate_set(c[0], c[1]));
+				if ca == cc {
+					continue;
+				}
+
+				let step = eliminate(b, Technique::XYZWing, ca & cc, &[pivot, a, c]);
+				if step.is_some() {
+					return step;
+				}
+			}
+		}
+	}
+	None
+}
+
+//Two cells that do not see each other both hold only x and y, and x can only go in two cells of some unit,
+//one seeing each of them. One of those two cells gets x, so one of the pair is y, and y can go in no cell
+//that sees both of the pair.
+pub(crate) fn w_wing(b: &Board) -> Option<Step> {
+	let bivalue = cells_with(b, |cand| cand.len() == 2);
+
+	for (k, &a) in bivalue.iter().enumerate() {
+		let pair = b.candidate_set(a[0], a[1]);
+		for &c in &bivalue[k+1..] {
+			if b.candidate_set(c[0], c[1]) != pair || sees(b, a, c) {
+				continue;
+			}
+
+			for x in pair {
+				let y = pair - DigitSet::single(x);
+				for unit in &b.topology().units {
+					let places: Vec<[usize; 2]> = unit.iter().copied().filter(|each| b.candidate_set(each[0], each[1]).contains(x)).collect();
+					if places.len() != 2 || places.contains(&a) || places.contains(&c) {
+						continue;
+					}
+
+					let linked = (sees(b, places[0], a) && sees(b, places[1], c)) || (sees(b, places[0], c) && sees(b, places[1], a));
+					if !linked {
+						continue;
+					}
+
+					let step = eliminate(b, Technique::WWing, y, &[a, c]);
+					if step.is_some() {
+						return step;
+					}
+				}
+			}
+		}
+	}
+	None
+}
+
+//Peers of a pivot whose candidates pass a filter
+fn pincers<F: Fn(DigitSet) -> bool>(b: &Board, pivot: [usize; 2], filter: F) -> Vec<[usize; 2]> {
+	b.topology().peers[pivot[0]][pivot[1]].aoe.iter().copied().filter(|each| filter(b.candidate_set(each[0], each[1]))).collect()
+}
+
+//Step removing digits from every cell that sees all of some cells, if any of them hold one.
+fn eliminate(b: &Board, technique: Technique, digits: DigitSet, cells: &[[usize; 2]]) -> Option<Step> {
+	let mut eliminations = vec![];
+	for &each in &b.topology().peers[cells[0][0]][cells[0][1]].aoe {
+		if cells.contains(&each) || !cells[1..].iter().all(|&c| sees(b, each, c)) {
+			continue;
+		}
+		for d in b.candidate_set(each[0], each[1]) & digits {
+			eliminations.push((each, d));
+		}
+	}
+
+	Step::new(technique, eliminations)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn finds_xy_wing() {
+		let mut b = Board::from_pencilmarks("
+			46   2  9   8   3   1    46  5   7
+			45   16 7   459 2   49   3   8   16
+			8    3  145 456 46  7    2   14  9
+			7    5  12  469 469 3    68  19  268
+			19   8  6   7   5   2    149 349 13
+			239  4  23  1   8   69   5   7   26
+			1456 16 45  2   7   4689 189 39  138
+			124  7  234 349 149 489  189 6   5
+			136  9  8   36  16  5    7   2   4
+		");
+		let step = xy_wing(&b).unwrap();
+		assert_eq!(step.to_string(), "XY-Wing: r4c5<>6");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(3, 4), vec![4, 9]);
+	}
+
+	#[test]
+	fn finds_xyz_wing() {
+		let mut b = Board::from_pencilmarks("
+			3459 7    1345 6    19  58 13489 148 2
+			2359 2356 1235 58   129 4  3789  168 3789
+			249  8    1246 7    3   12 1469  5   149
+			35   35   7    9    4   6  2     18  18
+			1    246  248  238  5   7  349   46  349
+			24   9    2468 1238 12  38 346   7   5
+			6    345  345  345  8   9  17    2   17
+			7    1    235  235  6   35 48    9   48
+			8    24   9    124  7   12 5     3   6
+		");
+		let step = xyz_wing(&b).unwrap();
+		assert_eq!(step.to_string(), "XYZ-Wing: r5c3<>4");
+		b.apply_step(&step);
+		assert_eq!(b.candidates(4, 2), vec![2, 8]);
+	}
+
+	#[test]
+	fn finds_w_wing() {
+		let mut b = Board::from_pencilmarks("
+			6   1 458 345  9  38   248 78 247
+			3   9 48  24   7  28   6   1  5
+			458 7 2   456  15 16   3   9  48
+			2   6 1   9    3  5    48  78 47
+			57  3 9   27   8  4    125 6  12
+			457 8 457 1    6  27   25  3  9
+			9   4 678 67   2  167  18  5  3
+			1   5 3   8    4  9    7   2  6
+			78  2 67  3567 15 1367 9   4  18
+		");
+		let step = w_wing(&b).unwrap();
+		assert_eq!(step.to_string(), "W-Wing: r1c7<>4, r4c9<>4");
+		b.apply_step(&step);
+		//r4c9 is left with its 7, which is placed.
+		assert_eq!(b.candidates(0, 6), vec![2, 8]);
+		assert_eq!(b.digit(3, 8), 7);
+	}
+}
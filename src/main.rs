@@ -1,371 +1,330 @@
-use std::process::Command;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Write};
+use std::process;
 use std::time::{Instant};
 
-use colored::Colorize;
-extern crate winapi;
-
-use std::ptr;
-use std::io::{self, Write};
-use winapi::um::consoleapi::GetConsoleMode;
-use winapi::um::consoleapi::SetConsoleMode;
-use winapi::um::processenv::GetStdHandle;
-use winapi::um::winbase::STD_OUTPUT_HANDLE;
-use winapi::um::wincon::ENABLE_VIRTUAL_TERMINAL_PROCESSING;
-
-//Used to allow color printing
-fn enable_virtual_terminal_processing() -> io::Result<()> {
-    unsafe {
-        // Get the handle to the standard output (console)
-        let stdout_handle = GetStdHandle(STD_OUTPUT_HANDLE);
-        if stdout_handle == ptr::null_mut() {
-            return Err(io::Error::last_os_error());
-        }
-
-        // Get the current console mode
-        let mut mode: u32 = 0;
-        if GetConsoleMode(stdout_handle, &mut mode) == 0 {
-            return Err(io::Error::last_os_error());
-        }
-
-        // Enable ENABLE_VIRTUAL_TERMINAL_PROCESSING flag
-        mode |= ENABLE_VIRTUAL_TERMINAL_PROCESSING;
-        if SetConsoleMode(stdout_handle, mode) == 0 {
-            return Err(io::Error::last_os_error());
-        }
-    }
-
-    Ok(())
+use rust_pseudokude::{batch, console, parse, Board, Branching, Deduction, SolverBackend};
+
+const USAGE: &str = "Usage:
+  pseudokude solve [options] <file|->
+  pseudokude bench [options] --runs N <file|->
+  pseudokude validate <file|->
+  pseudokude logic [options] <file|->  Solve without guessing, listing every named step and rating the puzzle
+  pseudokude batch [options] <file|->  Solve one single-line puzzle per line
+  pseudokude [--no-pause]           Solve the built-in example board
+
+Options:
+  --animate / --no-animate          Show every backtracking guess (default: on when stdout is a terminal)
+  --format pretty|grid|line         Output format (default: pretty)
+  --color auto|always|never         Colored output (default: auto)
+  --branching first|mrv|unit        Cell to guess on when backtracking (default: mrv)
+  --backend candidates|dlx          Search engine, dlx uses Dancing Links (default: candidates)
+  --deduction singles|intersections|subsets|full
+                                    Candidate analysis after every guess, more means fewer but slower guesses (default: singles)
+  --threads N                       Search on N threads, 0 for every core, using candidates (default: 1)
+                                    For batch, solve N puzzles at a time with any backend
+  --runs N                          Number of times to solve when benchmarking (default: 1)
+  --unique                          Reject puzzles that do not have exactly one solution
+  --assume-unique                   Let logic use techniques that rely on the puzzle having one solution";
+
+//How a solved board is printed.
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+	Pretty, //Colored board with house separators, see Board::show()
+	Grid, //One row per line, readable by parse::grid
+	Line, //The whole board on one line, readable by parse::line
 }
 
-//Individual cell holding all aoe information.
-#[derive(Clone)]
-pub struct Cell {
-	digit: u16, //Digit of cell
-	row: Vec<[usize; 2]>, //Coordinates of cell's row
-	col: Vec<[usize; 2]>, //Coordinates of cell's col
-	house: Vec<[usize; 2]>, //Coordinates of cell's house
-	aoe: Vec<[usize; 2]>, //Coordinates of cell's aoe
-	cand: Vec<u16>, //candidates of current cell
-	cand_limit: Vec<u16>, //Restrictions on candidates
-	was_empty: bool,
-	known: bool,
+//Options shared by all subcommands.
+struct Options {
+	animate: bool,
+	format: Format,
+	runs: usize,
+	unique: bool,
+	assume_unique: bool,
+	branching: Branching,
+	backend: SolverBackend,
+	deduction: Deduction,
+	threads: usize,
+	path: Option<String>,
 }
-impl Cell {
-
-	//Constructor
-	pub fn new() -> Self {
-		Self {
-			digit: 0,
-			row: vec![],
-			col: vec![],
-			house: vec![],
-			aoe: vec![],
-			cand: vec![],
-			cand_limit: vec![],
-			was_empty: false,
-			known: false,
+
+//Options batch has no use for, since it always writes one line per puzzle and reports multiple solutions itself.
+const BATCH_IGNORES: [&str; 6] = ["--animate", "--no-animate", "--format", "--unique", "--assume-unique", "--runs"];
+
+//Parse the arguments following the subcommand, refusing options the subcommand would ignore.
+fn parse_options(command: &str, args: &[String]) -> Result<Options, String> {
+	let mut options = Options {
+		animate: io::stdout().is_terminal(),
+		format: Format::Pretty,
+		runs: 1,
+		unique: false,
+		assume_unique: false,
+		branching: Branching::default(),
+		backend: SolverBackend::default(),
+		deduction: Deduction::default(),
+		threads: 1,
+		path: None,
+	};
+
+	let mut i = 0;
+	while i < args.len() {
+		if command == "batch" && BATCH_IGNORES.contains(&args[i].as_str()) {
+			return Err(format!("{} does not apply to batch", args[i]));
 		}
-	}
-}
 
-//Entire board containing size information and 2d vector of cells.
-#[derive(Clone)]
-pub struct Board {
-	bsize: usize, //Board side-length
-	hsize: usize, //House side-length
-	last_modified: [usize; 3], //Information about the last-modified cell.
-	cell: Vec<Vec<Cell>>, //2D vector containing all cells
-	solved: bool,
-}
-impl Board {
-
-	//Constructor
-	pub fn new(bsize: usize) -> Self {
-		Self {
-			bsize,
-			hsize: (bsize as f64).sqrt() as usize,
-			last_modified: [0,0,0],
-			cell: vec![],
-			solved: true,
+		match args[i].as_str() {
+			"--animate" => options.animate = true,
+			"--no-animate" => options.animate = false,
+			"--unique" => options.unique = true,
+			"--assume-unique" => options.assume_unique = true,
+			"--format" => {
+				i += 1;
+				options.format = match args.get(i).map(|s| s.as_str()) {
+					Some("pretty") => Format::Pretty,
+					Some("grid") => Format::Grid,
+					Some("line") => Format::Line,
+					_ => return Err(String::from("--format expects pretty, grid or line")),
+				};
+			}
+			"--color" => {
+				i += 1;
+				match args.get(i).map(|s| s.as_str()) {
+					Some("auto") => {}
+					Some("always") => colored::control::set_override(true),
+					Some("never") => colored::control::set_override(false),
+					_ => return Err(String::from("--color expects auto, always or never")),
+				}
+			}
+			"--branching" => {
+				i += 1;
+				options.branching = match args.get(i).map(|s| s.as_str()) {
+					Some("first") => Branching::FirstEmpty,
+					Some("mrv") => Branching::FewestCandidates,
+					Some("unit") => Branching::MostConstrainedUnit,
+					_ => return Err(String::from("--branching expects first, mrv or unit")),
+				};
+			}
+			"--backend" => {
+				i += 1;
+				options.backend = match args.get(i).map(|s| s.as_str()) {
+					Some("candidates") => SolverBackend::Candidates,
+					Some("dlx") => SolverBackend::DancingLinks,
+					_ => return Err(String::from("--backend expects candidates or dlx")),
+				};
+			}
+			"--deduction" => {
+				i += 1;
+				options.deduction = match args.get(i).map(|s| s.as_str()) {
+					Some("singles") => Deduction::Singles,
+					Some("intersections") => Deduction::Intersections,
+					Some("subsets") => Deduction::Subsets,
+					Some("full") => Deduction::Full,
+					_ => return Err(String::from("--deduction expects singles, intersections, subsets or full")),
+				};
+			}
+			"--threads" => {
+				i += 1;
+				options.threads = match args.get(i).and_then(|s| s.parse().ok()) {
+					Some(threads) => threads,
+					_ => return Err(String::from("--threads expects a number")),
+				};
+			}
+			"--runs" => {
+				i += 1;
+				options.runs = match args.get(i).and_then(|s| s.parse().ok()) {
+					Some(runs) if runs > 0 => runs,
+					_ => return Err(String::from("--runs expects a positive integer")),
+				};
+			}
+			arg if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+			arg => {
+				if options.path.is_some() {
+					return Err(format!("unexpected argument {}", arg));
+				}
+				options.path = Some(String::from(arg));
+			}
 		}
+		i += 1;
 	}
 
-	//Initialize values of board from given input, where init is the sudoku board.
-	fn init(&mut self, init: &Vec<Vec<u16>>) {
-
-		let mut hx: usize;
-		let mut hy: usize;
-
-		//Iterate through row
-		for i in 0..self.bsize {
+	Ok(options)
+}
 
-			//Initialize row
-			self.cell.push(Vec::new());
+//Read a board from a file, or from stdin when the path is "-".
+fn read_board(options: &Options) -> Result<Board, String> {
+	let path = match &options.path {
+		Some(path) => path,
+		None => return Err(String::from("missing puzzle file")),
+	};
+
+	let mut text = String::new();
+	if path == "-" {
+		io::stdin().read_to_string(&mut text).map_err(|e| format!("stdin: {}", e))?;
+	} else {
+		text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+	}
 
-			//Iterate through column
-			for j in 0.. self.bsize {
+	let grid = parse::puzzle(&text).map_err(|e| format!("{}: {}", path, e))?;
+	let mut b = Board::from_grid(&grid).map_err(|e| format!("{}: {}", path, e))?;
+	b.set_branching(options.branching.clone());
+	b.set_backend(options.backend);
+	b.set_deduction(options.deduction);
+	b.set_assume_unique(options.assume_unique);
 
-				//Initialize cell
-				self.cell[i].push(Cell::new());
+	//Catch boards that can't be printed before spending time on them.
+	if options.format == Format::Line && b.size() > parse::LINE_MAX {
+		return Err(format!("--format line only supports boards up to {}x{}", parse::LINE_MAX, parse::LINE_MAX));
+	}
+	Ok(b)
+}
 
-				//Assign digit to cell
-				self.cell[i][j].digit = init[i][j];
+//Ensure a board has exactly one solution when --unique is given.
+fn check_unique(b: &Board, options: &Options) -> Result<(), String> {
+	if !options.unique {
+		return Ok(());
+	}
 
-				if self.cell[i][j].digit == 0 {
-					self.cell[i][j].was_empty = true;
-				}
+	let count = b.count_solutions(2);
+	if !count.is_unique() {
+		return Err(format!("puzzle does not have a unique solution ({} solutions)", count));
+	}
+	Ok(())
+}
 
-				//Initialize row and column coordinates
-				for k in 0..self.bsize {
-					if k != j {
-						self.cell[i][j].row.push([i,k]);
-					}
-					if k != i {
-						self.cell[i][j].col.push([k,j]);
-					}
-				}
+//Print a board in the requested format.
+fn print_board(b: &Board, format: Format) -> Result<(), String> {
+	match format {
+		Format::Pretty => b.show(),
+		Format::Grid => print!("{}", b),
+		Format::Line => println!("{}", parse::to_line(&b.to_grid()).map_err(|e| e.to_string())?),
+	}
+	Ok(())
+}
 
-				//The top-left coordinate for the cell's house
-				hy = (((i/self.hsize) as f64).floor() as usize)*(self.hsize);
-				hx = (((j/self.hsize) as f64).floor() as usize)*(self.hsize);
-
-				//Iterate from top-left of house and add to cell's house and aoe coordinates.
-				for k in 0..self.hsize {
-					for l in 0..self.hsize {
-						if i != (k+hy) || j != (l+hx) {
-							self.cell[i][j].house.push([(k+hy),(l+hx)]);
-							self.cell[i][j].aoe.push([(k+hy),(l+hx)]);
-						}
-					}
-				}
+//Solve a single board and print the solution.
+fn solve(options: &Options) -> Result<(), String> {
+	let b = read_board(options)?;
+	check_unique(&b, options)?;
 
-				//Initialize AOE coordinates
-				for k in (self.hsize-(j%self.hsize)+j)..self.bsize {
-					self.cell[i][j].aoe.push([i, k]); //Row after house
-				}
+	let solution = if options.animate && options.format == Format::Pretty {
+		b.solve_with(|b| b.show())
+	} else {
+		b.solve_parallel(options.threads)
+	};
 
-				for k in 0..(j+(self.hsize-(j%self.hsize))-self.hsize) {
-					self.cell[i][j].aoe.push([i, k]); //Row before house
-				}
+	match solution {
+		Ok(solution) => print_board(solution.board(), options.format),
+		Err(e) => Err(e.to_string()),
+	}
+}
 
-				for k in (self.hsize-(i%self.hsize)+i)..self.bsize {
-					self.cell[i][j].aoe.push([k, j]); //Column after house
-				}
+//Solve the same board a number of times and print the average time.
+fn bench(options: &Options) -> Result<(), String> {
+	let b = read_board(options)?;
+	check_unique(&b, options)?;
+	let mut final_avg: f64 = 0.0;
+	let mut solution = None;
+
+	for _ in 0..options.runs {
+		let start = Instant::now();
+		solution = Some(b.solve_parallel(options.threads).map_err(|e| e.to_string())?);
+		final_avg += start.elapsed().as_secs_f64() * 1000.0;
+	}
 
-				for k in 0..(i+(self.hsize-(i%self.hsize))-self.hsize) {
-					self.cell[i][j].aoe.push([k, j]); //Column before house
-				}
-			}
-		}
+	//Show the solved board
+	if let Some(solution) = solution {
+		print_board(solution.board(), options.format)?;
 	}
+	final_avg /= options.runs as f64;
+	println!("Average time to solve: {}ms ({} runs)", final_avg, options.runs);
+	Ok(())
+}
 
-	//Show current state of board
-	fn show(&self) {
-
-		let mut output = String::from("");
-
-		//How much space, including whitespace, each digit needs.
-		let space_per_digit = ((self.bsize as f64).log10()+2.0).floor() as usize;
-		
-		print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-		output.push_str("\n");
-
-		output.push_str(&format!("{} - Original puzzle\n", "White\t"));
-		output.push_str(&format!("{} - Solved via candidate analysis\n", "Red\t".red()));
-		output.push_str(&format!("{} - Current backtracking cell\n", "Blue\t".cyan()));
-		output.push_str(&format!("{} - Solved via backtracking, other candidates still exist\n", "Yellow\t".yellow()));
-		output.push_str(&format!("{} - Solved via backtracking, no more candidates exist\n\n", "Green\t".green()));
-
-		//Main loop
-		for i in 0..self.bsize {
-			for j in 0..self.bsize {
-
-				//Ensure enough white-space before digit.
-				if self.cell[i][j].digit != 0 {
-					for _ in 0..space_per_digit-(((self.cell[i][j].digit).checked_ilog10().unwrap_or(0)+2) as usize) {
-						output.push_str(" ");
-					}
-
-					//Color cell depending on if it was solved via backtracking or candidate elimination.
-					if self.cell[i][j].known == true {
-						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().green()));
-					} else if self.cell[i][j].was_empty == true && (i*9+j) < (self.last_modified[0]*9+self.last_modified[1]) {
-						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().yellow()));
-					} else if i == self.last_modified[0] && j == self.last_modified[1] {
-						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().cyan()));
-					} else if self.cell[i][j].was_empty == true {
-						output.push_str(&format!("{}", self.cell[i][j].digit.to_string().red()));
-					} else {
-						output.push_str(&format!("{}", self.cell[i][j].digit.to_string()));
-					}
-				} else {
-					for _ in 0..space_per_digit-1 {
-						output.push_str(" ");
-					}
-				}
+//Check that a board can be read, has no conflicting givens and has a solution.
+fn validate(options: &Options) -> Result<(), String> {
+	let b = read_board(options)?;
 
-				//Add vertical line when end of house is reached.
-				if (j+1) % self.hsize == 0 && (j+1) != (self.bsize) {
-					output.push_str("|");
-				} else {
-					output.push_str(" ");
-				}
-			}
-			output.push_str("\n");
-
-			//Add horizontal line when end of house is reached.
-			if (i+1) % self.hsize == 0 && (i+1) != (self.bsize) {
-				for k in 0..self.hsize { 
-					for _ in 0..(self.hsize*space_per_digit)-1 {
-						output.push_str("―");
-					}
-					if k != self.hsize-1 {
-						output.push_str("+");
-					}
-				}
-				output.push_str("\n");
-			}
+	//Report every problem with the givens at once
+	if let Err(errors) = b.validate() {
+		for e in &errors {
+			println!("{}", e);
 		}
-
-		print!("{}", output);
+		return Err(format!("invalid: {} problem(s) found", errors.len()));
 	}
+	check_unique(&b, options).map_err(|e| format!("invalid: {}", e))?;
 
-	//Returns a vector of digits OR candidates from a vector of coordinates
-	fn coords_to_digits(&self, area: &Vec<[usize; 2]>, return_cand: bool) -> Vec<u16> {
-		let mut output: Vec<u16> = vec![];
-
-		//Iterate through area
-		for each in area {
-
-			//Whether to return area's digits or all of area's candidates
-			if return_cand {
-				if self.cell[each[0]][each[1]].digit == 0 {
-					for each in &self.cell[each[0]][each[1]].cand {
-						output.push(*each);
-					}
-				}
-			} else {
-				if self.cell[each[0]][each[1]].digit != 0 {
-					output.push(self.cell[each[0]][each[1]].digit);
-				}
-			}
+	match b.solve() {
+		Ok(_) => {
+			println!("valid");
+			Ok(())
 		}
-		return output;
+		Err(e) => Err(format!("invalid: {}", e)),
 	}
+}
 
-	//Updates the candidates of all cells, restricted by cand_limit.
-	fn update_cand(&mut self, coord: [usize; 2]) {
-		let mut aoe: Vec<u16>; //Current cell's house
-		let mut cand_len: u16;
-		
-		for each in &self.cell[coord[0]][coord[1]].aoe.clone() {
-			if self.cell[each[0]][each[1]].digit == 0 {
-
-				cand_len = 0;
-				self.cell[each[0]][each[1]].cand.clear();
-
-				//Assign all candidates, restricted by limit and cand_limit.
-				aoe = self.coords_to_digits(&self.cell[each[0]][each[1]].aoe, false);
-
-				for k in 1..(self.bsize+1) {
-					if !aoe.contains(&(k as u16)) && !self.cell[each[0]][each[1]].cand_limit.contains(&(k as u16)) {
-						self.cell[each[0]][each[1]].cand.push(k as u16);
-						cand_len += 1;
-					}
-				}
+//Solve a board by candidate analysis alone and print every named step it took.
+fn logic(options: &Options) -> Result<(), String> {
+	let b = read_board(options)?;
+	let result = b.solve_logically().map_err(|e| e.to_string())?;
 
-				//If there is only 1 candidate, set it as the digit and restart.
-				if cand_len == 1 {
-					self.cell[each[0]][each[1]].digit = self.cell[each[0]][each[1]].cand[0];
-					self.update_cand([each[0], each[1]]);
-				}
-			}
-		}
+	//The pretty board clears the screen, so it goes first.
+	print_board(result.board(), options.format)?;
+	for step in result.steps() {
+		println!("{}", step);
 	}
 
-	//Updates the candidates of all cells, restricted by cand_limit.
-	fn update_all_cand(&mut self) {
-		let mut aoe: Vec<u16>; //Current cell's house
-		
-		//Iterate through cells
-		for i in 0..self.bsize {
-			for j in 0..self.bsize {
-				//Ensure cell is a 0
-				if self.cell[i][j].digit == 0 {
-
-					self.cell[i][j].cand.clear();
-
-					aoe = self.coords_to_digits(&self.cell[i][j].aoe, false);
-
-					//Assign all candidates, restricted by limit and cand_limit.
-					for k in 1..(self.bsize+1) {
-						if !aoe.contains(&(k as u16)) && !self.cell[i][j].cand_limit.contains(&(k as u16)) {
-							self.cell[i][j].cand.push(k as u16);
-						}
-					}
-				}
-			}
-		}
-		
+	if result.is_solved() {
+		println!("Solved without guessing ({} steps)", result.steps().len());
+	} else {
+		println!("Guessing still needed after {} steps", result.steps().len());
 	}
+	println!("Rating: {}", result.rating());
+	Ok(())
+}
 
-	
-	//Checks for cells that have candidates that are unique to one of its areas
-	fn process_of_elimination(&mut self) {
-		let mut c: u16; //Current cell's candidate
-		let mut row: Vec<u16>; //Current cell's row
-		let mut col: Vec<u16>; //Current cell's col
-		let mut house: Vec<u16>; //Current cell's house
-		let mut reset: bool = true; //Whether or not to keep searching
-
-		//Show board during calculation. (SUPER SLOWDOWN)
-		//self.show();
-		//Start search
-		while reset {
-			self.solved = true;
-			reset = false;
-
-			//Iterate through all cells
-			for i in 0..self.bsize {
-				for j in 0..self.bsize {
-
-					//Ensure cell is a 0
-					if self.cell[i][j].digit == 0 {
-						self.solved = false;
-
-						//Establish candidates in each area
-						row = self.coords_to_digits(&self.cell[i][j].row, true);
-						col = self.coords_to_digits(&self.cell[i][j].col, true);
-						house = self.coords_to_digits(&self.cell[i][j].house, true);
-
-						//If areas do not contain candidate, then set cell to candidate.
-						for k in 0..self.cell[i][j].cand.len() {
-							c = self.cell[i][j].cand[k];
-							if !row.contains(&c) || !col.contains(&c) || !house.contains(&c) {
-								self.cell[i][j].digit = c;
-								self.update_cand([i, j]);
-								reset = true;
-							}
-						}
-					}
-				}
-			}
-		}
+//Solve every line of input on one thread or spread over several.
+fn solve_lines<R: BufRead, W: Write>(input: R, output: W, options: &Options) -> io::Result<batch::Stats> {
+	let search = batch::SearchOptions {
+		backend: options.backend,
+		branching: options.branching.clone(),
+		deduction: options.deduction,
+	};
+
+	if options.threads == 1 {
+		batch::solve_lines_with(input, output, &search)
+	} else {
+		batch::solve_lines_parallel(input, output, options.threads, &search)
 	}
 }
 
-
-//Wait for user input, just invokes Batch pause>nul.
-fn pause() {
-	let _ = Command::new("cmd.exe").arg("/c").arg("pause>nul").status();
+//Solve every puzzle of a file, one per line, and print the aggregate timing.
+fn solve_batch(options: &Options) -> Result<(), String> {
+	let path = match &options.path {
+		Some(path) => path,
+		None => return Err(String::from("missing puzzle file")),
+	};
+
+	let stdout = io::stdout();
+	let stats = if path == "-" {
+		solve_lines(io::stdin().lock(), stdout.lock(), options)
+	} else {
+		let file = File::open(path).map_err(|e| format!("{}: {}", path, e))?;
+		solve_lines(BufReader::new(file), stdout.lock(), options)
+	};
+
+	match stats {
+		Ok(stats) => {
+			eprintln!("{}", stats);
+			Ok(())
+		}
+		Err(e) => Err(format!("{}: {}", path, e)),
+	}
 }
 
-//Main code containing backtracking logic.
-fn main() {
-	if let Err(e) = enable_virtual_terminal_processing() {
-        writeln!(io::stderr(), "Error enabling virtual terminal processing: {}", e).unwrap();
-    }
-
+//Solve the built-in example board, as Pseudokude did before it had subcommands.
+fn demo(no_pause: bool) {
 	let init = vec![
 				vec![0,0,0,0,0,0,0,0,0],
 				vec![4,0,0,0,0,0,3,0,0],
@@ -377,75 +336,77 @@ fn main() {
 				vec![0,0,0,0,9,0,0,6,0],
 				vec![0,0,0,0,0,7,0,0,8]];
 
-	let mut b = Board::new(init.len()); //The main board
-	let mut b_stack: Vec<Board> = vec![]; //The stack of boards
-
-	b.init(&init); //Initialize cells and area coordinates
-	b.update_all_cand(); //Update the candidates for all cells
-	b.process_of_elimination(); //candidates initialization
-	b_stack.push(b.clone()); //Push first unsolved board to stack.
-	b_stack.push(b.clone());
-	//Main back-tracking loop
-	while b.solved == false {
-
-		//Update temporary board
-		b = b_stack.last_mut().unwrap().clone();
-
-		//Iterate through cells
-		'outer: for i in 0..b.bsize {
-			for j in 0..b.bsize {
-
-				//Ensure cell is a 0
-				if b.cell[i][j].digit == 0 {
-					
-					//Ensure cell has candidates
-					if b.cell[i][j].cand.len() > 0 {
-						
-						//Set cell to first candidate and update the last-modified cell data.
-						b.cell[i][j].digit = b.cell[i][j].cand[0];
-						if b.cell[i][j].cand.len() == 1 {
-							b.cell[i][j].known = true;
-						}
-						b.last_modified = [i, j, b.cell[i][j].cand[0] as usize];
-
-						//Update candidates and check for area candidate eliminations.
-						b.update_cand([i, j]);
-						b.process_of_elimination();
-						
-						//Push board to stack
-						b_stack.push(b.clone());
-
-						b.show();
-						
-					//No candidates mean the current board state is impossible to solve.
-					} else {
-
-						//Only encountered if the board is unsolvable, which means it was entered incorrectly.
-						if b_stack.len() == 1 {
-							panic!("ERROR - Sudoku board not entered correctly.");
-						}
-
-						//Pop top of stack.
-						b_stack.pop();
-
-						//Revert the last-modified cell to a 0 and update its cand_limit list.
-						b_stack.last_mut().unwrap().cell[b.last_modified[0]][b.last_modified[1]].cand_limit.push(b.last_modified[2] as u16);
-						b_stack.last_mut().unwrap().cell[b.last_modified[0]][b.last_modified[1]].digit = 0;
-						
-						//Update candidates and check for area candidate eliminations.
-						b_stack.last_mut().unwrap().update_all_cand();
-						b_stack.last_mut().unwrap().process_of_elimination();
-						//Restart search
-						break 'outer;
-					}
-				}
-			}
+	//Solve while showing every backtracking guess, in row-major order so the colors read top to bottom
+	let solution = Board::from_grid(&init).and_then(|mut b| {
+		b.set_branching(Branching::FirstEmpty);
+		b.solve_with(|b| b.show())
+	});
+	match solution {
+		//Show the solved board
+		Ok(solution) => solution.board().show(),
+		Err(e) => eprintln!("ERROR - {}", e),
+	}
+
+	if !no_pause {
+		console::pause();
+	}
+}
+
+fn main() {
+	console::setup();
+
+	let args: Vec<String> = env::args().skip(1).collect();
+
+	let result = match args.first().map(|s| s.as_str()) {
+		None => {
+			demo(false);
+			return;
+		}
+		Some("--no-pause") if args.len() == 1 => {
+			demo(true);
+			return;
+		}
+		Some("solve") => parse_options("solve", &args[1..]).and_then(|options| solve(&options)),
+		Some("bench") => parse_options("bench", &args[1..]).and_then(|options| bench(&options)),
+		Some("validate") => parse_options("validate", &args[1..]).and_then(|options| validate(&options)),
+		Some("logic") => parse_options("logic", &args[1..]).and_then(|options| logic(&options)),
+		Some("batch") => parse_options("batch", &args[1..]).and_then(|options| solve_batch(&options)),
+		Some("-h") | Some("--help") | Some("help") => {
+			println!("{}", USAGE);
+			return;
 		}
+		Some(command) => Err(format!("unknown command {}\n\n{}", command, USAGE)),
+	};
+
+	if let Err(e) = result {
+		eprintln!("pseudokude: {}", e);
+		process::exit(1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	//Run solve on a puzzle written to a temporary file.
+	fn solve_text(name: &str, text: &str) -> Result<(), String> {
+		let path = env::temp_dir().join(format!("pseudokude-{}-{}.txt", name, process::id()));
+		fs::write(&path, text).unwrap();
+		let args = vec![String::from("--no-animate"), path.to_string_lossy().into_owned()];
+		let result = parse_options("solve", &args).and_then(|options| solve(&options));
+		fs::remove_file(&path).unwrap();
+		result
 	}
-	
-	//Show the solved board
-	b_stack.last_mut().unwrap().show();
 
-	pause();
+	#[test]
+	fn reports_bad_puzzles() {
+		let e = solve_text("ragged", "1 2 3 4\n0 0 0\n0 0 0 0\n0 0 0 0\n").unwrap_err();
+		assert!(e.ends_with("Row 2 has 3 cells, which differs from the number of rows"), "{}", e);
 
+		let e = solve_text("range", "1 0 0 0\n0 0 0 0\n0 0 0 5\n0 0 0 0\n").unwrap_err();
+		assert!(e.ends_with("Digit 5 at row 3, column 4 is out of range"), "{}", e);
+
+		let e = solve_text("conflict", "0 2 0 0\n0 0 0 0\n0 0 0 0\n0 2 0 0\n").unwrap_err();
+		assert_eq!(e, "Digit 2 appears at both row 1, column 2 and row 4, column 2");
+	}
 }
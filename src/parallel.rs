@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+use crate::board::{Solution, Solutions};
+
+//State shared by every worker of a parallel search.
+struct Shared {
+	queue: Mutex<Queue>, //Branches waiting for a worker
+	ready: Condvar, //Signalled when a branch is queued or the search ends
+	waiting: AtomicUsize, //Number of idle workers waiting for a branch
+	queued: AtomicUsize, //Number of branches in the queue
+	found: AtomicBool, //Set once any worker finds a solution, cancelling every other branch
+	solution: Mutex<Option<Solution>>, //The first solution found
+}
+
+//Branches waiting for a worker, along with how many workers are still searching.
+struct Queue {
+	branches: Vec<Solutions>,
+	busy: usize,
+}
+
+//Search a board on a number of threads until one of them finds a solution.
+//Idle workers steal work by asking busy ones to split off the untried candidates of their earliest guess,
+//so the search tree is divided at its top levels first.
+pub(crate) fn search(root: Solutions, threads: usize) -> Option<Solution> {
+	let shared = Shared {
+		queue: Mutex::new(Queue { branches: vec![root], busy: 0 }),
+		ready: Condvar::new(),
+		waiting: AtomicUsize::new(0),
+		queued: AtomicUsize::new(1),
+		found: AtomicBool::new(false),
+		solution: Mutex::new(None),
+	};
+
+	thread::scope(|scope| {
+		for _ in 0..threads {
+			scope.spawn(|| work(&shared));
+		}
+	});
+
+	shared.solution.into_inner().unwrap()
+}
+
+//Take branches off the queue until a solution is found or no worker has anything left to search.
+fn work(shared: &Shared) {
+	loop {
+		//Wait for a branch, giving up once every other worker is idle too.
+		let mut branch = {
+			let mut queue = shared.queue.lock().unwrap();
+			shared.waiting.fetch_add(1, Ordering::SeqCst);
+			loop {
+				if shared.found.load(Ordering::SeqCst) || (queue.branches.is_empty() && queue.busy == 0) {
+					shared.ready.notify_all();
+					return;
+				}
+				if let Some(branch) = queue.branches.pop() {
+					shared.queued.fetch_sub(1, Ordering::SeqCst);
+					shared.waiting.fetch_sub(1, Ordering::SeqCst);
+					queue.busy += 1;
+					break branch;
+				}
+				queue = shared.ready.wait(queue).unwrap();
+			}
+		};
+
+		//Search the branch, splitting off work whenever another worker is idle.
+		let solution = branch.search(&mut |s| {
+			if shared.found.load(Ordering::Relaxed) {
+				return false;
+			}
+			if shared.waiting.load(Ordering::Relaxed) > shared.queued.load(Ordering::Relaxed) {
+				if let Some(split) = s.split_off() {
+					let mut queue = shared.queue.lock().unwrap();
+					queue.branches.push(split);
+					shared.queued.fetch_add(1, Ordering::SeqCst);
+					shared.ready.notify_one();
+				}
+			}
+			true
+		});
+
+		if let Some(solution) = solution {
+			let mut first = shared.solution.lock().unwrap();
+			if first.is_none() {
+				*first = Some(solution);
+			}
+			shared.found.store(true, Ordering::SeqCst);
+		}
+
+		let mut queue = shared.queue.lock().unwrap();
+		queue.busy -= 1;
+		shared.ready.notify_all();
+	}
+}
@@ -0,0 +1,213 @@
+use crate::error::ParseError;
+
+//Largest board side-length line() and to_line() can encode, one character per cell.
+pub const LINE_MAX: usize = 25;
+
+//Parse a board written as one row per line, with digits separated by whitespace or commas.
+//Empty cells are written as 0 or '.', blank lines and lines starting with '#' are skipped.
+pub fn grid(text: &str) -> Result<Vec<Vec<u16>>, ParseError> {
+	let mut output: Vec<Vec<u16>> = vec![];
+
+	//Iterate through lines
+	for (i, line) in text.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		let mut row: Vec<u16> = vec![];
+
+		//Iterate through digits of the row
+		for token in line.split(|c: char| c.is_whitespace() || c == ',').filter(|t| !t.is_empty()) {
+			if token == "." {
+				row.push(0);
+			} else {
+				match token.parse::<u16>() {
+					Ok(digit) => row.push(digit),
+					Err(_) => return Err(ParseError::new(i+1, format!("'{}' is not a digit", token))),
+				}
+			}
+		}
+		output.push(row);
+	}
+
+	if output.is_empty() {
+		return Err(ParseError::new(0, String::from("no board found")));
+	}
+	Ok(output)
+}
+
+//Parse a board written on a single line, such as "..3.2.6..9..3.5..1...", with '.' or '0' for empty cells.
+//Boards up to 16x16 use 1-9 then A-G, larger boards up to 25x25 use A-Y.
+//Anything after the first whitespace, such as a rating or comment, is ignored.
+pub fn line(text: &str) -> Result<Vec<Vec<u16>>, ParseError> {
+	let symbols: Vec<char> = text.split_whitespace().next().unwrap_or("").chars().collect();
+
+	//Find the board side-length, which must also be a square.
+	let bsize = (symbols.len() as f64).sqrt() as usize;
+	let hsize = (bsize as f64).sqrt() as usize;
+	if symbols.is_empty() || bsize*bsize != symbols.len() || hsize*hsize != bsize {
+		return Err(ParseError::new(0, format!("{} cells is not a valid board size", symbols.len())));
+	}
+	if bsize > LINE_MAX {
+		return Err(no_encoding(bsize));
+	}
+
+	let mut output: Vec<Vec<u16>> = vec![];
+	for i in 0..bsize {
+		let mut row: Vec<u16> = vec![];
+		for j in 0..bsize {
+			match symbol_to_digit(symbols[i*bsize+j], bsize) {
+				Some(digit) => row.push(digit),
+				None => return Err(ParseError::new(0, format!("'{}' is not a digit of a {}x{} board", symbols[i*bsize+j], bsize, bsize))),
+			}
+		}
+		output.push(row);
+	}
+	Ok(output)
+}
+
+//Parse a board in either format. A single line without commas whose first token holds as many characters
+//as a board has cells, such as 16 or 81, is read as a single-line board, anything else as a grid.
+pub fn puzzle(text: &str) -> Result<Vec<Vec<u16>>, ParseError> {
+	let mut lines = text.lines().map(|l| l.trim()).filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+	if let (Some(first), None) = (lines.next(), lines.next()) {
+		if !first.contains(',') && first.split_whitespace().next().is_some_and(|t| is_cell_count(t.chars().count())) {
+			return line(first);
+		}
+	}
+	grid(text)
+}
+
+//Whether a number of cells makes up a square board of 4x4 or larger
+fn is_cell_count(cells: usize) -> bool {
+	let bsize = (cells as f64).sqrt() as usize;
+	let hsize = (bsize as f64).sqrt() as usize;
+	bsize > 1 && bsize*bsize == cells && hsize*hsize == bsize
+}
+
+//Write a board of up to 25x25 on a single line, the inverse of line().
+pub fn to_line(grid: &[Vec<u16>]) -> Result<String, ParseError> {
+	let bsize = grid.len();
+	if bsize > LINE_MAX {
+		return Err(no_encoding(bsize));
+	}
+	Ok(grid.iter().flatten().map(|&digit| digit_to_symbol(digit, bsize)).collect())
+}
+
+//Error for a board too large to write on a single line
+fn no_encoding(bsize: usize) -> ParseError {
+	ParseError::new(0, format!("{}x{} boards have no single-character encoding", bsize, bsize))
+}
+
+//Convert a single-line symbol into a digit, 0 for an empty cell.
+fn symbol_to_digit(symbol: char, bsize: usize) -> Option<u16> {
+	let digit = match symbol.to_ascii_uppercase() {
+		'.' | '0' => 0,
+		c @ '1'..='9' if bsize <= 16 => c as u16 - '0' as u16,
+		c @ 'A'..='Z' if bsize <= 16 => c as u16 - 'A' as u16 + 10,
+		c @ 'A'..='Z' => c as u16 - 'A' as u16 + 1,
+		_ => return None,
+	};
+
+	if digit as usize > bsize {
+		return None;
+	}
+	Some(digit)
+}
+
+//Convert a digit into its single-line symbol, '.' for an empty cell.
+fn digit_to_symbol(digit: u16, bsize: usize) -> char {
+	match digit {
+		0 => '.',
+		1..=9 if bsize <= 16 => (b'0' + digit as u8) as char,
+		_ if bsize <= 16 => (b'A' + (digit - 10) as u8) as char,
+		_ => (b'A' + (digit - 1) as u8) as char,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	//Solved board of any size, shifting each row so no row, column or house repeats a digit
+	fn solved(bsize: usize) -> Vec<Vec<u16>> {
+		let hsize = (bsize as f64).sqrt() as usize;
+		(0..bsize).map(|i| (0..bsize).map(|j| ((i%hsize*hsize + i/hsize + j) % bsize + 1) as u16).collect()).collect()
+	}
+
+	//Solved board with every third cell emptied
+	fn puzzle_of(bsize: usize) -> Vec<Vec<u16>> {
+		let mut grid = solved(bsize);
+		for (i, row) in grid.iter_mut().enumerate() {
+			for (j, digit) in row.iter_mut().enumerate() {
+				if (i*bsize + j).is_multiple_of(3) {
+					*digit = 0;
+				}
+			}
+		}
+		grid
+	}
+
+	#[test]
+	fn line_round_trip() {
+		for bsize in [4, 9, 16, 25] {
+			let grid = puzzle_of(bsize);
+			let text = to_line(&grid).unwrap();
+			assert_eq!(text.len(), bsize*bsize);
+			assert_eq!(line(&text).unwrap(), grid);
+			assert_eq!(puzzle(&text).unwrap(), grid);
+		}
+	}
+
+	#[test]
+	fn line_symbols() {
+		let grid = solved(16);
+		assert!(to_line(&grid).unwrap().starts_with("123456789ABCDEFG"));
+		let grid = solved(25);
+		assert!(to_line(&grid).unwrap().starts_with("ABCDEFGHIJKLMNOPQRSTUVWXY"));
+		assert_eq!(line(&"abcdefghijklmnopqrstuvwxy".repeat(25)).unwrap()[0][24], 25);
+	}
+
+	#[test]
+	fn line_rejects_large_boards() {
+		assert!(to_line(&solved(36)).is_err());
+		assert!(line(&".".repeat(36*36)).is_err());
+	}
+
+	#[test]
+	fn line_ignores_trailing_text() {
+		let text = to_line(&puzzle_of(9)).unwrap();
+		assert_eq!(puzzle(&format!("{} rated 2.3", text)).unwrap(), puzzle_of(9));
+	}
+
+	#[test]
+	fn detects_grids() {
+		//Several rows
+		let text = "1 0 0 0\n0 0 3 0\n0 4 0 0\n0 0 0 2\n";
+		assert_eq!(puzzle(text).unwrap(), grid(text).unwrap());
+		assert_eq!(puzzle(text).unwrap()[1][2], 3);
+
+		//A single row, even with tokens longer than one digit
+		assert_eq!(puzzle("10 0 3").unwrap(), vec![vec![10, 0, 3]]);
+		assert_eq!(puzzle("12").unwrap(), vec![vec![12]]);
+
+		//Commas, comments and blank lines
+		assert_eq!(puzzle("# comment\n\n1,2\n3,4\n").unwrap(), vec![vec![1, 2], vec![3, 4]]);
+		assert_eq!(puzzle("1.3.").unwrap_err(), grid("1.3.").unwrap_err());
+	}
+
+	#[test]
+	fn detects_single_lines() {
+		assert_eq!(puzzle("1.3.....2.4.....").unwrap()[0], vec![1, 0, 3, 0]);
+		assert_eq!(puzzle(&format!("\n# comment\n{}\n", ".".repeat(81))).unwrap(), vec![vec![0; 9]; 9]);
+	}
+
+	#[test]
+	fn reports_bad_digits() {
+		assert_eq!(grid("1 x\n").unwrap_err().line(), 1);
+		assert!(line(&format!("A{}", ".".repeat(80))).is_err());
+		assert!(line(&".".repeat(80)).is_err());
+	}
+}
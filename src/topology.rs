@@ -0,0 +1,100 @@
+//Coordinates of the cells sharing an area with one cell.
+pub(crate) struct Peers {
+	pub(crate) row: Vec<[usize; 2]>, //Coordinates of cell's row
+	pub(crate) col: Vec<[usize; 2]>, //Coordinates of cell's col
+	pub(crate) house: Vec<[usize; 2]>, //Coordinates of cell's house
+	pub(crate) aoe: Vec<[usize; 2]>, //Coordinates of cell's aoe
+}
+
+//Peer structure of every cell of a board size, built once and shared by every copy of a board.
+pub(crate) struct Topology {
+	pub(crate) peers: Vec<Vec<Peers>>, //2D vector containing the peers of all cells
+	pub(crate) units: Vec<Vec<[usize; 2]>>, //Coordinates of every row, then every column, then every house
+}
+impl Topology {
+
+	//Initialize the row, column, house and aoe coordinates of every cell.
+	pub(crate) fn new(bsize: usize, hsize: usize) -> Self {
+		let mut peers: Vec<Vec<Peers>> = vec![];
+		let mut hx: usize;
+		let mut hy: usize;
+
+		//Iterate through row
+		for i in 0..bsize {
+
+			//Initialize row
+			peers.push(Vec::new());
+
+			//Iterate through column
+			for j in 0..bsize {
+
+				//Initialize cell
+				let mut p = Peers {
+					row: vec![],
+					col: vec![],
+					house: vec![],
+					aoe: vec![],
+				};
+
+				//Initialize row and column coordinates
+				for k in 0..bsize {
+					if k != j {
+						p.row.push([i,k]);
+					}
+					if k != i {
+						p.col.push([k,j]);
+					}
+				}
+
+				//The top-left coordinate for the cell's house
+				hy = (i/hsize)*hsize;
+				hx = (j/hsize)*hsize;
+
+				//Iterate from top-left of house and add to cell's house and aoe coordinates.
+				for k in 0..hsize {
+					for l in 0..hsize {
+						if i != (k+hy) || j != (l+hx) {
+							p.house.push([(k+hy),(l+hx)]);
+							p.aoe.push([(k+hy),(l+hx)]);
+						}
+					}
+				}
+
+				//Initialize AOE coordinates
+				for k in (hsize-(j%hsize)+j)..bsize {
+					p.aoe.push([i, k]); //Row after house
+				}
+
+				for k in 0..(j+(hsize-(j%hsize))-hsize) {
+					p.aoe.push([i, k]); //Row before house
+				}
+
+				for k in (hsize-(i%hsize)+i)..bsize {
+					p.aoe.push([k, j]); //Column after house
+				}
+
+				for k in 0..(i+(hsize-(i%hsize))-hsize) {
+					p.aoe.push([k, j]); //Column before house
+				}
+
+				peers[i].push(p);
+			}
+		}
+
+		//Every row, column and house, each including all of its cells
+		let mut units: Vec<Vec<[usize; 2]>> = vec![];
+		for i in 0..bsize {
+			units.push((0..bsize).map(|j| [i, j]).collect());
+		}
+		for j in 0..bsize {
+			units.push((0..bsize).map(|i| [i, j]).collect());
+		}
+		for h in 0..bsize {
+			hy = (h/hsize)*hsize;
+			hx = (h%hsize)*hsize;
+			units.push((0..bsize).map(|k| [hy + k/hsize, hx + k%hsize]).collect());
+		}
+
+		Self { peers, units }
+	}
+}